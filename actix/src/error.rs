@@ -0,0 +1,60 @@
+use std::fmt::{self, Display, Formatter};
+
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use json_api::{doc::ErrorObject, Error};
+
+use crate::response;
+
+/// An error document that can be returned from a handler. Also used as the error of
+/// the extractors in this crate.
+///
+/// Converting an [`Error`] describes errors caused by the request in detail. Other
+/// errors respond with `500 Internal Server Error` and do not expose their details.
+///
+/// [`Error`]: https://docs.rs/json-api/0.4/json_api/error/struct.Error.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErrorResponse(pub ErrorObject);
+
+impl ErrorResponse {
+    /// Consumes the `ErrorResponse` wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> ErrorObject {
+        self.0
+    }
+}
+
+impl Display for ErrorResponse {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.0.detail.as_ref().or(self.0.title.as_ref()) {
+            Some(message) => f.write_str(message),
+            None => f.write_str("unknown error"),
+        }
+    }
+}
+
+impl From<Error> for ErrorResponse {
+    fn from(error: Error) -> Self {
+        ErrorResponse(ErrorObject::from(&error))
+    }
+}
+
+impl From<ErrorObject> for ErrorResponse {
+    fn from(error: ErrorObject) -> Self {
+        ErrorResponse(error)
+    }
+}
+
+impl ResponseError for ErrorResponse {
+    fn status_code(&self) -> StatusCode {
+        self.0
+            .status
+            .and_then(|status| StatusCode::from_u16(status.as_u16()).ok())
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        match json_api::doc::error_to_vec(self.0.clone()) {
+            Ok(body) => response::with_body(self.status_code(), body),
+            Err(_) => HttpResponse::InternalServerError().finish(),
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use std::future::{self, Future, Ready};
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+
+use actix_web::{
+    dev,
+    http::header::{HeaderMap, ACCEPT, CONTENT_TYPE},
+    web, FromRequest, HttpMessage, HttpRequest,
+};
+use serde::de::DeserializeOwned;
+
+use json_api::{
+    self,
+    doc::{NewObject, Object},
+    media_type::{self, MediaType, Negotiated},
+    query::Query as JsonApiQuery,
+    Error,
+};
+
+use crate::error::ErrorResponse;
+
+/// The limit of a request document in bytes.
+pub const DATA_LIMIT: usize = 10 * 1024 * 1024;
+
+/// Extracts a request document that creates a resource, for example in a handler of
+/// `POST /posts`.
+///
+/// The content negotiation rules of [`Negotiate`] are enforced before the request body
+/// is read.
+///
+/// [`Negotiate`]: ./struct.Negotiate.html
+#[derive(Debug)]
+pub struct Create<T: DeserializeOwned>(pub T);
+
+impl<T: DeserializeOwned> Create<T> {
+    /// Consumes the `Create` wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: DeserializeOwned> Deref for Create<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: DeserializeOwned> DerefMut for Create<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: DeserializeOwned + 'static> FromRequest for Create<T> {
+    type Error = ErrorResponse;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut dev::Payload) -> Self::Future {
+        let negotiated = negotiate(req.headers());
+        let payload = web::Payload::from_request(req, payload);
+
+        Box::pin(async move {
+            negotiated?;
+
+            let body = read(payload).await?;
+            let value = json_api::doc::from_body::<NewObject, _>(&body)?;
+
+            Ok(Create(value))
+        })
+    }
+}
+
+/// Extracts a request document that updates a resource, for example in a handler of
+/// `PATCH /posts/1`.
+///
+/// The content negotiation rules of [`Negotiate`] are enforced before the request body
+/// is read.
+///
+/// [`Negotiate`]: ./struct.Negotiate.html
+#[derive(Debug)]
+pub struct Update<T: DeserializeOwned>(pub T);
+
+impl<T: DeserializeOwned> Update<T> {
+    /// Consumes the `Update` wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: DeserializeOwned> Deref for Update<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: DeserializeOwned> DerefMut for Update<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: DeserializeOwned + 'static> FromRequest for Update<T> {
+    type Error = ErrorResponse;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut dev::Payload) -> Self::Future {
+        let negotiated = negotiate(req.headers());
+        let payload = web::Payload::from_request(req, payload);
+
+        Box::pin(async move {
+            negotiated?;
+
+            let body = read(payload).await?;
+            let value = json_api::doc::from_body::<Object, _>(&body)?;
+
+            Ok(Update(value))
+        })
+    }
+}
+
+/// An extractor that enforces the content negotiation rules of the JSON API
+/// specification.
+///
+/// Fails with `415 Unsupported Media Type` if the `Content-Type` of the request is not
+/// the JSON API media type or has parameters other than `profile`, and with `406 Not
+/// Acceptable` if every instance of the JSON API media type in the `Accept` header has
+/// parameters other than `profile`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Negotiate {
+    /// The media type of the request document.
+    pub content_type: MediaType,
+
+    /// The media type that the client accepts for the response document.
+    pub accept: MediaType,
+}
+
+impl From<Negotiated> for Negotiate {
+    fn from(negotiated: Negotiated) -> Self {
+        Negotiate {
+            content_type: negotiated.content_type,
+            accept: negotiated.accept,
+        }
+    }
+}
+
+impl FromRequest for Negotiate {
+    type Error = ErrorResponse;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut dev::Payload) -> Self::Future {
+        future::ready(negotiate(req.headers()).map_err(ErrorResponse::from))
+    }
+}
+
+/// Extracts the query parameters of a request.
+///
+/// Fails with `400 Bad Request` if the query string can not be parsed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    inner: JsonApiQuery,
+}
+
+impl Query {
+    /// Consumes the [`Query`] wrapper and returns the wrapped value.
+    ///
+    /// [`Query`]: ./struct.Query.html
+    pub fn into_inner(self) -> JsonApiQuery {
+        self.inner
+    }
+}
+
+impl Deref for Query {
+    type Target = JsonApiQuery;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for Query {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl FromRequest for Query {
+    type Error = ErrorResponse;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut dev::Payload) -> Self::Future {
+        let result = cached_query(req)
+            .map(|inner| Query { inner })
+            .map_err(ErrorResponse::from);
+
+        future::ready(result)
+    }
+}
+
+/// Returns the parsed query of `req`.
+///
+/// The query is parsed once and cached in the extensions of the request, so the
+/// [`Query`] extractor and the responders of a request share the same value.
+///
+/// [`Query`]: ./struct.Query.html
+pub(crate) fn cached_query(req: &HttpRequest) -> Result<JsonApiQuery, Error> {
+    if let Some(query) = req.extensions().get::<JsonApiQuery>() {
+        return Ok(query.clone());
+    }
+
+    let query = json_api::query::from_str(req.query_string())?;
+
+    req.extensions_mut().insert(query.clone());
+    Ok(query)
+}
+
+/// Checks the headers of a request with [`media_type::negotiate`]. Actix Web has its own
+/// header types, so they can not be passed to `http_ext::negotiate`.
+///
+/// [`media_type::negotiate`]: https://docs.rs/json-api/0.4/json_api/media_type/fn.negotiate.html
+fn negotiate(headers: &HeaderMap) -> Result<Negotiate, Error> {
+    let header = |name| {
+        headers
+            .get(name)
+            .map(|value| value.to_str().unwrap_or_default())
+    };
+
+    Ok(media_type::negotiate(header(CONTENT_TYPE), header(ACCEPT), &[])?.into())
+}
+
+/// Reads a request document, up to [`DATA_LIMIT`] bytes.
+///
+/// A payload that can not be read is reported as an invalid member at the root of the
+/// document, like a body that is not valid JSON.
+///
+/// [`DATA_LIMIT`]: ./constant.DATA_LIMIT.html
+async fn read<F>(payload: F) -> Result<web::Bytes, Error>
+where
+    F: Future<Output = Result<web::Payload, actix_web::Error>>,
+{
+    let payload = payload
+        .await
+        .map_err(|e| Error::invalid_member("", &e.to_string()))?;
+
+    match payload.to_bytes_limited(DATA_LIMIT).await {
+        Ok(Ok(bytes)) => Ok(bytes),
+        Ok(Err(e)) => Err(Error::invalid_member("", &e.to_string())),
+        Err(_) => Err(Error::payload_too_large(DATA_LIMIT as u64)),
+    }
+}
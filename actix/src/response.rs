@@ -0,0 +1,264 @@
+use std::iter::FromIterator;
+use std::ops::{Deref, DerefMut};
+
+use actix_web::{
+    body::BoxBody,
+    http::{header, StatusCode},
+    HttpRequest, HttpResponse, Responder, ResponseError,
+};
+use json_api::{
+    self,
+    doc::{Identifier, Object},
+    media_type::MEDIA_TYPE,
+    view::Linkage,
+    Error, Resource,
+};
+
+use crate::{error::ErrorResponse, request};
+
+/// Renders a collection of resources, for example in response to `GET /posts`.
+///
+/// The query of the request is used to render sparse fieldsets and included
+/// resources.
+#[derive(Debug)]
+pub struct Collection<T: Resource>(pub Vec<T>);
+
+impl<T: Resource> Collection<T> {
+    /// Consumes the [`Collection`] wrapper and returns the wrapped value.
+    ///
+    /// [`Collection`]: ./struct.Collection.html
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T: Resource> Deref for Collection<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Resource> DerefMut for Collection<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Resource> FromIterator<T> for Collection<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Collection(Vec::from_iter(iter))
+    }
+}
+
+impl<T: Resource> Responder for Collection<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        request::cached_query(req)
+            .and_then(|query| json_api::to_vec::<_, Object>(&*self, Some(&query)))
+            .map_or_else(fail, |body| with_body(StatusCode::OK, body))
+    }
+}
+
+/// Renders a resource that was created, for example in response to `POST /posts`.
+///
+/// Responds with `201 Created`. The `Location` header of the response is the `self`
+/// link of the rendered resource, if it has one.
+#[derive(Debug)]
+pub struct Created<T: Resource>(pub T);
+
+impl<T: Resource> Created<T> {
+    /// Consumes the [`Created`] wrapper and returns the wrapped value.
+    ///
+    /// [`Created`]: ./struct.Created.html
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Resource> Deref for Created<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Resource> DerefMut for Created<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Resource> Responder for Created<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        let doc = match request::cached_query(req)
+            .and_then(|query| json_api::to_doc::<_, Object>(&*self, Some(&query)))
+        {
+            Ok(doc) => doc,
+            Err(e) => return fail(e),
+        };
+
+        // The location of a new resource matches its `self` link.
+        let location = doc.links().get("self").map(|link| link.to_string());
+
+        match json_api::to_vec(doc, None) {
+            Ok(body) => {
+                let mut resp = with_body(StatusCode::CREATED, body);
+
+                if let Some(value) = location.and_then(|loc| loc.parse().ok()) {
+                    resp.headers_mut().insert(header::LOCATION, value);
+                }
+
+                resp
+            }
+            Err(e) => fail(e),
+        }
+    }
+}
+
+/// Renders a single resource, for example in response to `GET /posts/1`.
+///
+/// The query of the request is used to render sparse fieldsets and included
+/// resources.
+#[derive(Debug)]
+pub struct Member<T: Resource>(pub T);
+
+impl<T: Resource> Member<T> {
+    /// Consumes the [`Member`] wrapper and returns the wrapped value.
+    ///
+    /// [`Member`]: ./struct.Member.html
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Resource> Deref for Member<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Resource> DerefMut for Member<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Resource> Responder for Member<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        request::cached_query(req)
+            .and_then(|query| json_api::to_vec::<_, Object>(&*self, Some(&query)))
+            .map_or_else(fail, |body| with_body(StatusCode::OK, body))
+    }
+}
+
+/// Renders the linkage of a to-many relationship, for example in response to
+/// `GET /posts/1/relationships/comments`.
+///
+/// The `self` and `related` links of the document are derived from the path of the
+/// request.
+#[derive(Debug)]
+pub struct ToMany<T: Resource>(pub Vec<T>);
+
+impl<T: Resource> ToMany<T> {
+    /// Consumes the [`ToMany`] wrapper and returns the wrapped value.
+    ///
+    /// [`ToMany`]: ./struct.ToMany.html
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T: Resource> Deref for ToMany<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Resource> DerefMut for ToMany<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Resource> FromIterator<T> for ToMany<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        ToMany(Vec::from_iter(iter))
+    }
+}
+
+impl<T: Resource> Responder for ToMany<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        json_api::to_vec::<_, Identifier>(Linkage::new(&*self, req.path()), None)
+            .map_or_else(fail, |body| with_body(StatusCode::OK, body))
+    }
+}
+
+/// Renders the linkage of a to-one relationship, for example in response to
+/// `GET /posts/1/relationships/author`. A value of `None` is rendered as `null`.
+///
+/// The `self` and `related` links of the document are derived from the path of the
+/// request.
+#[derive(Debug)]
+pub struct ToOne<T: Resource>(pub Option<T>);
+
+impl<T: Resource> ToOne<T> {
+    /// Consumes the [`ToOne`] wrapper and returns the wrapped value.
+    ///
+    /// [`ToOne`]: ./struct.ToOne.html
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T: Resource> Deref for ToOne<T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Resource> DerefMut for ToOne<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Resource> Responder for ToOne<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        json_api::to_vec::<_, Identifier>(Linkage::new(self.as_ref(), req.path()), None)
+            .map_or_else(fail, |body| with_body(StatusCode::OK, body))
+    }
+}
+
+pub(crate) fn with_body(status: StatusCode, body: Vec<u8>) -> HttpResponse {
+    HttpResponse::build(status)
+        .insert_header((header::CONTENT_TYPE, MEDIA_TYPE))
+        .body(body)
+}
+
+fn fail(e: Error) -> HttpResponse {
+    ErrorResponse::from(e).error_response()
+}
@@ -0,0 +1,121 @@
+use actix_http::{error::PayloadError, h1, Request};
+use actix_web::{
+    http::{header, StatusCode},
+    test::{self, TestRequest},
+    web, App,
+};
+use json_api_actix::{Create, Query};
+
+const MEDIA_TYPE: &str = "application/vnd.api+json";
+
+async fn create(Create(post): Create<serde_json::Value>) -> String {
+    post["title"].as_str().unwrap_or_default().to_owned()
+}
+
+async fn fields(query: Query) -> String {
+    json_api::query::to_string(&query).unwrap()
+}
+
+async fn send(req: TestRequest) -> (StatusCode, web::Bytes) {
+    call(req.to_request()).await
+}
+
+async fn call(req: Request) -> (StatusCode, web::Bytes) {
+    let app = test::init_service(
+        App::new()
+            .route("/posts", web::post().to(create))
+            .route("/fields", web::get().to(fields)),
+    )
+    .await;
+
+    let resp = test::call_service(&app, req).await;
+    let status = resp.status();
+
+    (status, test::read_body(resp).await)
+}
+
+fn create_request(content_type: &str, body: &'static str) -> TestRequest {
+    TestRequest::post()
+        .uri("/posts")
+        .insert_header((header::CONTENT_TYPE, content_type))
+        .set_payload(body)
+}
+
+#[actix_web::test]
+async fn create_extracts_document() {
+    let req = create_request(
+        MEDIA_TYPE,
+        r#"{"data":{"type":"posts","attributes":{"title":"Hello"}}}"#,
+    );
+
+    let (status, body) = send(req).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(&body[..], b"Hello");
+}
+
+#[actix_web::test]
+async fn create_rejects_truncated_body() {
+    let (mut sender, payload) = h1::Payload::create(false);
+    sender.set_error(PayloadError::Incomplete(None));
+
+    let req = TestRequest::post()
+        .uri("/posts")
+        .insert_header((header::CONTENT_TYPE, MEDIA_TYPE))
+        .to_request();
+
+    let (req, _) = req.replace_payload(payload.into());
+    let (status, body) = call(req).await;
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"][0]["source"]["pointer"], "");
+}
+
+#[actix_web::test]
+async fn create_rejects_invalid_json() {
+    let (status, body) = send(create_request(MEDIA_TYPE, r#"{"data":"#)).await;
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"][0]["status"], "400");
+    assert_eq!(body["errors"][0]["source"]["pointer"], "");
+}
+
+#[actix_web::test]
+async fn create_rejects_unsupported_media_type() {
+    let value = format!("{}; charset=utf-8", MEDIA_TYPE);
+    let (status, body) = send(create_request(&value, "{}")).await;
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(body["errors"][0]["status"], "415");
+}
+
+#[actix_web::test]
+async fn create_rejects_unacceptable_media_type() {
+    let value = format!("{}; charset=utf-8", MEDIA_TYPE);
+    let req = create_request(MEDIA_TYPE, "{}").insert_header((header::ACCEPT, value));
+
+    let (status, _) = send(req).await;
+    assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+}
+
+#[actix_web::test]
+async fn query_extracts_parameters() {
+    let req = TestRequest::get().uri("/fields?fields[posts]=title");
+    let (status, body) = send(req).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(&body[..], b"fields[posts]=title");
+}
+
+#[actix_web::test]
+async fn query_rejects_invalid_parameters() {
+    let req = TestRequest::get().uri("/fields?page[number]=2&page[offset]=10");
+    let (status, body) = send(req).await;
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["errors"][0]["detail"].is_string());
+}
@@ -0,0 +1,95 @@
+#[macro_use]
+extern crate json_api;
+
+use actix_web::{
+    http::{header, StatusCode},
+    test::{self, TestRequest},
+    web, App,
+};
+use json_api::Error;
+use json_api_actix::{Collection, Created, ErrorResponse, Member, ToMany, ToOne};
+
+struct Post(u64);
+
+resource!(Post, |&self| {
+    kind "posts";
+    id self.0;
+    link "self", format!("/posts/{}", self.0);
+});
+
+async fn call(uri: &str) -> (StatusCode, Option<String>, serde_json::Value) {
+    let app = test::init_service(
+        App::new()
+            .route(
+                "/posts",
+                web::get().to(|| async { Collection(vec![Post(1), Post(2)]) }),
+            )
+            .route("/posts/{id}", web::get().to(|| async { Member(Post(1)) }))
+            .route("/created", web::get().to(|| async { Created(Post(3)) }))
+            .route(
+                "/error",
+                web::get()
+                    .to(|| async { Err::<String, _>(ErrorResponse::from(Error::from("secret"))) }),
+            )
+            .route(
+                "/posts/{id}/relationships/comments",
+                web::get().to(|| async { ToMany(vec![Post(2)]) }),
+            )
+            .route(
+                "/posts/{id}/relationships/author",
+                web::get().to(|| async { ToOne::<Post>(None) }),
+            ),
+    )
+    .await;
+
+    let resp = test::call_service(&app, TestRequest::get().uri(uri).to_request()).await;
+    let status = resp.status();
+    let location = resp
+        .headers()
+        .get(header::LOCATION)
+        .map(|value| value.to_str().unwrap().to_owned());
+
+    assert_eq!(
+        resp.headers().get(header::CONTENT_TYPE).unwrap(),
+        "application/vnd.api+json"
+    );
+
+    let body = test::read_body(resp).await;
+    (status, location, serde_json::from_slice(&body).unwrap())
+}
+
+#[actix_web::test]
+async fn responders() {
+    // The path, the expected status, and the expected `Location` header.
+    let cases = [
+        ("/posts", StatusCode::OK, None),
+        ("/posts/1?include=author", StatusCode::OK, None),
+        ("/posts/1?page[number]=abc", StatusCode::BAD_REQUEST, None),
+        ("/created", StatusCode::CREATED, Some("/posts/3")),
+        ("/error", StatusCode::INTERNAL_SERVER_ERROR, None),
+    ];
+
+    for &(uri, status, location) in &cases {
+        let (actual, header, body) = call(uri).await;
+
+        assert_eq!(actual, status, "{}", uri);
+        assert_eq!(header.as_deref(), location, "{}", uri);
+
+        // Errors caused by the server do not expose their details.
+        assert_eq!(
+            body["errors"][0].get("detail").is_some(),
+            status == StatusCode::BAD_REQUEST
+        );
+    }
+}
+
+#[actix_web::test]
+async fn relationship_links_use_request_path() {
+    for uri in &[
+        "/posts/7/relationships/comments",
+        "/posts/7/relationships/author",
+    ] {
+        let (_, _, body) = call(uri).await;
+        assert_eq!(body["links"]["self"], *uri);
+    }
+}
@@ -0,0 +1,40 @@
+use axum::{
+    body::Body,
+    response::{IntoResponse, Response},
+};
+use json_api::{doc::ErrorObject, http_ext, Error};
+
+/// An error document that can be returned from a handler. Also used as the rejection
+/// of the extractors in this crate.
+///
+/// Converting an [`Error`] describes errors caused by the request in detail. Other
+/// errors respond with `500 Internal Server Error` and do not expose their details.
+///
+/// [`Error`]: https://docs.rs/json-api/0.4/json_api/error/struct.Error.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErrorResponse(pub ErrorObject);
+
+impl ErrorResponse {
+    /// Consumes the `ErrorResponse` wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> ErrorObject {
+        self.0
+    }
+}
+
+impl From<Error> for ErrorResponse {
+    fn from(error: Error) -> Self {
+        ErrorResponse(ErrorObject::from(&error))
+    }
+}
+
+impl From<ErrorObject> for ErrorResponse {
+    fn from(error: ErrorObject) -> Self {
+        ErrorResponse(error)
+    }
+}
+
+impl IntoResponse for ErrorResponse {
+    fn into_response(self) -> Response {
+        http_ext::error_response(self.0).map(Body::from)
+    }
+}
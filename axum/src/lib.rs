@@ -0,0 +1,22 @@
+//! Axum support for the json-api crate.
+//!
+//! Extract request documents and query parameters with [`Create`], [`Update`] and
+//! [`Query`], and respond with documents with [`Member`], [`Collection`], [`Created`],
+//! [`ToOne`] and [`ToMany`]. Errors are rendered as error documents by
+//! [`ErrorResponse`].
+//!
+//! [`Create`]: ./request/struct.Create.html
+//! [`Update`]: ./request/struct.Update.html
+//! [`Query`]: ./request/struct.Query.html
+//! [`Member`]: ./response/struct.Member.html
+//! [`Collection`]: ./response/struct.Collection.html
+//! [`Created`]: ./response/struct.Created.html
+//! [`ToOne`]: ./response/struct.ToOne.html
+//! [`ToMany`]: ./response/struct.ToMany.html
+//! [`ErrorResponse`]: ./error/struct.ErrorResponse.html
+
+pub mod error;
+pub mod request;
+pub mod response;
+
+pub use self::{error::ErrorResponse, request::*, response::*};
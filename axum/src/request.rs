@@ -0,0 +1,213 @@
+use std::ops::{Deref, DerefMut};
+
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, FromRequestParts, Request},
+    http::request::Parts,
+};
+use http_body_util::{BodyExt, LengthLimitError, Limited};
+use serde::de::DeserializeOwned;
+
+use json_api::{
+    self,
+    doc::{NewObject, Object},
+    http_ext,
+    media_type::{MediaType, Negotiated},
+    query::Query as JsonApiQuery,
+    Error,
+};
+
+use crate::error::ErrorResponse;
+
+/// The limit of a request document in bytes.
+pub const DATA_LIMIT: usize = 10 * 1024 * 1024;
+
+/// Extracts a request document that creates a resource, for example in a handler of
+/// `POST /posts`.
+///
+/// The content negotiation rules of [`Negotiate`] are enforced before the request body
+/// is read.
+///
+/// [`Negotiate`]: ./struct.Negotiate.html
+#[derive(Debug)]
+pub struct Create<T: DeserializeOwned>(pub T);
+
+impl<T: DeserializeOwned> Create<T> {
+    /// Consumes the `Create` wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: DeserializeOwned> Deref for Create<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: DeserializeOwned> DerefMut for Create<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<S, T> FromRequest<S> for Create<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = ErrorResponse;
+
+    async fn from_request(req: Request, _: &S) -> Result<Self, Self::Rejection> {
+        http_ext::negotiate(req.headers(), &[])?;
+
+        let body = read(req).await?;
+        let value = json_api::doc::from_body::<NewObject, _>(&body)?;
+
+        Ok(Create(value))
+    }
+}
+
+/// Extracts a request document that updates a resource, for example in a handler of
+/// `PATCH /posts/1`.
+///
+/// The content negotiation rules of [`Negotiate`] are enforced before the request body
+/// is read.
+///
+/// [`Negotiate`]: ./struct.Negotiate.html
+#[derive(Debug)]
+pub struct Update<T: DeserializeOwned>(pub T);
+
+impl<T: DeserializeOwned> Update<T> {
+    /// Consumes the `Update` wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: DeserializeOwned> Deref for Update<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: DeserializeOwned> DerefMut for Update<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<S, T> FromRequest<S> for Update<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = ErrorResponse;
+
+    async fn from_request(req: Request, _: &S) -> Result<Self, Self::Rejection> {
+        http_ext::negotiate(req.headers(), &[])?;
+
+        let body = read(req).await?;
+        let value = json_api::doc::from_body::<Object, _>(&body)?;
+
+        Ok(Update(value))
+    }
+}
+
+/// An extractor that enforces the content negotiation rules of the JSON API
+/// specification.
+///
+/// Rejects the request with `415 Unsupported Media Type` if the `Content-Type` of the
+/// request is not the JSON API media type or has parameters other than `profile`, and
+/// with `406 Not Acceptable` if every instance of the JSON API media type in the
+/// `Accept` header has parameters other than `profile`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Negotiate {
+    /// The media type of the request document.
+    pub content_type: MediaType,
+
+    /// The media type that the client accepts for the response document.
+    pub accept: MediaType,
+}
+
+impl From<Negotiated> for Negotiate {
+    fn from(negotiated: Negotiated) -> Self {
+        Negotiate {
+            content_type: negotiated.content_type,
+            accept: negotiated.accept,
+        }
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Negotiate {
+    type Rejection = ErrorResponse;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        Ok(http_ext::negotiate(&parts.headers, &[])?.into())
+    }
+}
+
+/// Extracts the query parameters of a request.
+///
+/// Rejects the request with `400 Bad Request` if the query string can not be parsed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    inner: JsonApiQuery,
+}
+
+impl Query {
+    /// Consumes the [`Query`] wrapper and returns the wrapped value.
+    ///
+    /// [`Query`]: ./struct.Query.html
+    pub fn into_inner(self) -> JsonApiQuery {
+        self.inner
+    }
+}
+
+impl Deref for Query {
+    type Target = JsonApiQuery;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for Query {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl From<JsonApiQuery> for Query {
+    fn from(inner: JsonApiQuery) -> Self {
+        Query { inner }
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Query {
+    type Rejection = ErrorResponse;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        Ok(Query {
+            inner: http_ext::query(&parts.uri)?,
+        })
+    }
+}
+
+/// Reads a request document, up to [`DATA_LIMIT`] bytes.
+///
+/// A body that can not be read, for example because it is truncated, is reported as an
+/// invalid member at the root of the document, like a body that is not valid JSON.
+///
+/// [`DATA_LIMIT`]: ./constant.DATA_LIMIT.html
+async fn read(req: Request) -> Result<Bytes, Error> {
+    match Limited::new(req.into_body(), DATA_LIMIT).collect().await {
+        Ok(collected) => Ok(collected.to_bytes()),
+        Err(e) if e.is::<LengthLimitError>() => Err(Error::payload_too_large(DATA_LIMIT as u64)),
+        Err(e) => Err(Error::invalid_member("", &e.to_string())),
+    }
+}
@@ -0,0 +1,244 @@
+use std::ops::{Deref, DerefMut};
+
+use axum::{
+    body::Body,
+    http::{header, HeaderValue, StatusCode, Uri},
+    response::{IntoResponse, Response},
+};
+use json_api::{
+    self,
+    doc::{Identifier, Object},
+    http_ext,
+    view::Linkage,
+    Error, Resource,
+};
+
+use crate::{error::ErrorResponse, request::Query};
+
+/// Renders a collection of resources, for example in response to `GET /posts`.
+///
+/// The [`Query`] of the request is used to render sparse fieldsets and included
+/// resources.
+///
+/// [`Query`]: ../request/struct.Query.html
+#[derive(Debug)]
+pub struct Collection<T: Resource>(pub Vec<T>, pub Query);
+
+impl<T: Resource> Collection<T> {
+    /// Consumes the [`Collection`] wrapper and returns the wrapped value.
+    ///
+    /// [`Collection`]: ./struct.Collection.html
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T: Resource> Deref for Collection<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Resource> DerefMut for Collection<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Resource> IntoResponse for Collection<T> {
+    fn into_response(self) -> Response {
+        respond(http_ext::response::<_, Object>(&*self.0, Some(&self.1)))
+    }
+}
+
+/// Renders a resource that was created, for example in response to `POST /posts`.
+///
+/// Responds with `201 Created`. The `Location` header of the response is the `self`
+/// link of the rendered resource, if it has one.
+#[derive(Debug)]
+pub struct Created<T: Resource>(pub T, pub Query);
+
+impl<T: Resource> Created<T> {
+    /// Consumes the [`Created`] wrapper and returns the wrapped value.
+    ///
+    /// [`Created`]: ./struct.Created.html
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Resource> Deref for Created<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Resource> DerefMut for Created<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Resource> IntoResponse for Created<T> {
+    fn into_response(self) -> Response {
+        let doc = match json_api::to_doc::<_, Object>(&self.0, Some(&self.1)) {
+            Ok(doc) => doc,
+            Err(e) => return fail(e),
+        };
+
+        // The location of a new resource matches its `self` link.
+        let location = doc
+            .links()
+            .get("self")
+            .and_then(|link| HeaderValue::from_str(&link.to_string()).ok());
+
+        let mut resp = respond(http_ext::response(doc, None));
+
+        if resp.status() == StatusCode::OK {
+            *resp.status_mut() = StatusCode::CREATED;
+
+            if let Some(location) = location {
+                resp.headers_mut().insert(header::LOCATION, location);
+            }
+        }
+
+        resp
+    }
+}
+
+/// Renders a single resource, for example in response to `GET /posts/1`.
+///
+/// The [`Query`] of the request is used to render sparse fieldsets and included
+/// resources.
+///
+/// [`Query`]: ../request/struct.Query.html
+#[derive(Debug)]
+pub struct Member<T: Resource>(pub T, pub Query);
+
+impl<T: Resource> Member<T> {
+    /// Consumes the [`Member`] wrapper and returns the wrapped value.
+    ///
+    /// [`Member`]: ./struct.Member.html
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Resource> Deref for Member<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Resource> DerefMut for Member<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Resource> IntoResponse for Member<T> {
+    fn into_response(self) -> Response {
+        respond(http_ext::response::<_, Object>(&self.0, Some(&self.1)))
+    }
+}
+
+/// Renders the linkage of a to-many relationship, for example in response to
+/// `GET /posts/1/relationships/comments`.
+///
+/// The `self` and `related` links of the document are derived from the [`Uri`] of the
+/// request.
+///
+/// [`Uri`]: https://docs.rs/http/1/http/uri/struct.Uri.html
+#[derive(Debug)]
+pub struct ToMany<T: Resource>(pub Vec<T>, pub Uri);
+
+impl<T: Resource> ToMany<T> {
+    /// Consumes the [`ToMany`] wrapper and returns the wrapped value.
+    ///
+    /// [`ToMany`]: ./struct.ToMany.html
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T: Resource> Deref for ToMany<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Resource> DerefMut for ToMany<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Resource> IntoResponse for ToMany<T> {
+    fn into_response(self) -> Response {
+        respond(http_ext::response::<_, Identifier>(
+            Linkage::new(&*self.0, self.1.path()),
+            None,
+        ))
+    }
+}
+
+/// Renders the linkage of a to-one relationship, for example in response to
+/// `GET /posts/1/relationships/author`. A value of `None` is rendered as `null`.
+///
+/// The `self` and `related` links of the document are derived from the [`Uri`] of the
+/// request.
+///
+/// [`Uri`]: https://docs.rs/http/1/http/uri/struct.Uri.html
+#[derive(Debug)]
+pub struct ToOne<T: Resource>(pub Option<T>, pub Uri);
+
+impl<T: Resource> ToOne<T> {
+    /// Consumes the [`ToOne`] wrapper and returns the wrapped value.
+    ///
+    /// [`ToOne`]: ./struct.ToOne.html
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T: Resource> Deref for ToOne<T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Resource> DerefMut for ToOne<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Resource> IntoResponse for ToOne<T> {
+    fn into_response(self) -> Response {
+        respond(http_ext::response::<_, Identifier>(
+            Linkage::new(self.0.as_ref(), self.1.path()),
+            None,
+        ))
+    }
+}
+
+fn respond(result: Result<axum::http::Response<Vec<u8>>, Error>) -> Response {
+    match result {
+        Ok(resp) => resp.map(Body::from),
+        Err(e) => fail(e),
+    }
+}
+
+fn fail(e: Error) -> Response {
+    ErrorResponse::from(e).into_response()
+}
@@ -0,0 +1,145 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use bytes::Bytes;
+use http_body::Frame;
+use http_body_util::BodyExt;
+use json_api_axum::{Create, Query};
+use tower::ServiceExt;
+
+const MEDIA_TYPE: &str = "application/vnd.api+json";
+
+async fn create(Create(post): Create<serde_json::Value>) -> String {
+    post["title"].as_str().unwrap_or_default().to_owned()
+}
+
+async fn fields(query: Query) -> String {
+    json_api::query::to_string(&query).unwrap()
+}
+
+fn app() -> Router {
+    Router::new()
+        .route("/posts", post(create))
+        .route("/fields", get(fields))
+}
+
+async fn send(req: Request<Body>) -> (StatusCode, serde_json::Value) {
+    let resp = app().oneshot(req).await.unwrap();
+    let status = resp.status();
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+fn create_request(content_type: &str, body: &str) -> Request<Body> {
+    Request::post("/posts")
+        .header(header::CONTENT_TYPE, content_type)
+        .body(Body::from(body.to_owned()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn create_extracts_document() {
+    let req = create_request(
+        MEDIA_TYPE,
+        r#"{"data":{"type":"posts","attributes":{"title":"Hello"}}}"#,
+    );
+
+    let resp = app().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"Hello");
+}
+
+/// A request body that fails before it is read completely.
+struct Truncated;
+
+impl http_body::Body for Truncated {
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        Poll::Ready(Some(Err(io::ErrorKind::UnexpectedEof.into())))
+    }
+}
+
+#[tokio::test]
+async fn create_rejects_truncated_body() {
+    let req = Request::post("/posts")
+        .header(header::CONTENT_TYPE, MEDIA_TYPE)
+        .body(Body::new(Truncated))
+        .unwrap();
+
+    let (status, body) = send(req).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"][0]["source"]["pointer"], "");
+}
+
+#[tokio::test]
+async fn create_rejects_invalid_json() {
+    let (status, body) = send(create_request(MEDIA_TYPE, r#"{"data":"#)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"][0]["status"], "400");
+    assert_eq!(body["errors"][0]["source"]["pointer"], "");
+}
+
+#[tokio::test]
+async fn create_rejects_unsupported_media_type() {
+    let value = format!("{}; charset=utf-8", MEDIA_TYPE);
+    let (status, body) = send(create_request(&value, "{}")).await;
+
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(body["errors"][0]["status"], "415");
+}
+
+#[tokio::test]
+async fn create_rejects_unacceptable_media_type() {
+    let mut req = create_request(MEDIA_TYPE, "{}");
+    let value = format!("{}; charset=utf-8", MEDIA_TYPE);
+
+    req.headers_mut()
+        .insert(header::ACCEPT, value.parse().unwrap());
+
+    let (status, _) = send(req).await;
+    assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+}
+
+#[tokio::test]
+async fn query_rejects_invalid_parameters() {
+    let req = Request::get("/fields?page[number]=2&page[offset]=10")
+        .body(Body::empty())
+        .unwrap();
+
+    let (status, body) = send(req).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["errors"][0]["detail"].is_string());
+}
+
+#[tokio::test]
+async fn query_extracts_parameters() {
+    let req = Request::get("/fields?fields[posts]=title")
+        .body(Body::empty())
+        .unwrap();
+
+    let resp = app().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"fields[posts]=title");
+}
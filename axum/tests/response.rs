@@ -0,0 +1,104 @@
+#[macro_use]
+extern crate json_api;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode, Uri},
+    routing::get,
+    Router,
+};
+use http_body_util::BodyExt;
+use json_api::Error;
+use json_api_axum::{Collection, Created, ErrorResponse, Member, Query, ToMany, ToOne};
+use tower::ServiceExt;
+
+struct Post(u64);
+
+resource!(Post, |&self| {
+    kind "posts";
+    id self.0;
+    link "self", format!("/posts/{}", self.0);
+});
+
+fn app() -> Router {
+    Router::new()
+        .route(
+            "/posts",
+            get(|query: Query| async move { Collection(vec![Post(1), Post(2)], query) }),
+        )
+        .route(
+            "/posts/{id}",
+            get(|query: Query| async move { Member(Post(1), query) }),
+        )
+        .route(
+            "/created",
+            get(|query: Query| async move { Created(Post(3), query) }),
+        )
+        .route(
+            "/error",
+            get(|| async { Err::<String, _>(ErrorResponse::from(Error::from("secret"))) }),
+        )
+        .route(
+            "/posts/{id}/relationships/comments",
+            get(|uri: Uri| async move { ToMany(vec![Post(2)], uri) }),
+        )
+        .route(
+            "/posts/{id}/relationships/author",
+            get(|uri: Uri| async move { ToOne::<Post>(None, uri) }),
+        )
+}
+
+#[tokio::test]
+async fn responders() {
+    // The path, the expected status, and the expected `Location` header.
+    let cases = [
+        ("/posts", StatusCode::OK, None),
+        ("/posts/1?include=author", StatusCode::OK, None),
+        ("/posts/1?page[number]=abc", StatusCode::BAD_REQUEST, None),
+        ("/created", StatusCode::CREATED, Some("/posts/3")),
+        ("/error", StatusCode::INTERNAL_SERVER_ERROR, None),
+    ];
+
+    for &(uri, status, location) in &cases {
+        let req = Request::get(uri).body(Body::empty()).unwrap();
+        let resp = app().oneshot(req).await.unwrap();
+
+        assert_eq!(resp.status(), status, "{}", uri);
+        assert_eq!(
+            resp.headers()
+                .get(header::LOCATION)
+                .map(|value| value.to_str().unwrap()),
+            location,
+            "{}",
+            uri
+        );
+        assert_eq!(
+            resp.headers()[header::CONTENT_TYPE],
+            "application/vnd.api+json"
+        );
+
+        // Errors caused by the server do not expose their details.
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let body = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+
+        assert_eq!(
+            body["errors"][0].get("detail").is_some(),
+            status == StatusCode::BAD_REQUEST
+        );
+    }
+}
+
+#[tokio::test]
+async fn relationship_links_use_request_path() {
+    for uri in &[
+        "/posts/7/relationships/comments",
+        "/posts/7/relationships/author",
+    ] {
+        let req = Request::get(*uri).body(Body::empty()).unwrap();
+        let resp = app().oneshot(req).await.unwrap();
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let body = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+
+        assert_eq!(body["links"]["self"], *uri);
+    }
+}
@@ -0,0 +1,327 @@
+use syn::{
+    meta::ParseNestedMeta, Attribute, Data, DeriveInput, Error, Fields, Ident, LitStr, Result,
+};
+
+/// The parsed `#[jsonapi(...)]` attributes of a struct.
+pub struct Container {
+    pub attrs: Vec<Attr>,
+    pub fields: Vec<Ident>,
+    pub id: Ident,
+    pub kind: LitStr,
+    pub links: Vec<Link>,
+    pub relationships: Vec<Rel>,
+}
+
+/// An attribute of the resource.
+pub struct Attr {
+    pub field: Ident,
+    pub name: LitStr,
+}
+
+/// A link of the resource or one of its relationships. The `href` may contain
+/// `{field}` placeholders that are replaced with the value of the field.
+pub struct Link {
+    pub href: LitStr,
+    pub name: LitStr,
+}
+
+/// The cardinality of a relationship.
+#[derive(Clone, Copy)]
+pub enum Cardinality {
+    One,
+    Many,
+}
+
+/// A relationship of the resource.
+pub struct Rel {
+    pub cardinality: Cardinality,
+    pub field: Ident,
+    pub links: Vec<Link>,
+    pub name: LitStr,
+}
+
+impl Container {
+    pub fn from_ast(input: &DeriveInput) -> Result<Self> {
+        let mut kind = None;
+        let mut links = Vec::new();
+
+        for attr in jsonapi_attrs(&input.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("kind") {
+                    kind = Some(member_name(&meta.value()?.parse()?)?);
+                    Ok(())
+                } else if meta.path.is_ident("link") {
+                    parse_links(&meta, &mut links)
+                } else {
+                    Err(meta.error("unsupported jsonapi attribute on a struct"))
+                }
+            })?;
+        }
+
+        let fields = match input.data {
+            Data::Struct(ref data) => match data.fields {
+                Fields::Named(ref fields) => &fields.named,
+                _ => {
+                    return Err(Error::new(
+                        input.ident.span(),
+                        "#[derive(Resource)] requires a struct with named fields",
+                    ))
+                }
+            },
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "#[derive(Resource)] can only be used on structs",
+                ))
+            }
+        };
+
+        let kind = kind.ok_or_else(|| {
+            Error::new(
+                input.ident.span(),
+                r#"missing #[jsonapi(kind = "...")] attribute"#,
+            )
+        })?;
+
+        let mut id = None;
+        let mut attrs = Vec::new();
+        let mut relationships = Vec::new();
+
+        for field in fields {
+            let ident = field.ident.clone().expect("named field");
+            let mut rel: Option<Rel> = None;
+            let mut rel_links = Vec::new();
+
+            for attr in jsonapi_attrs(&field.attrs) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("id") {
+                        if id.is_some() {
+                            return Err(meta.error("duplicate #[jsonapi(id)] attribute"));
+                        }
+
+                        id = Some(ident.clone());
+                        Ok(())
+                    } else if meta.path.is_ident("attr") {
+                        let name = parse_name(&meta, &ident)?;
+
+                        attrs.push(Attr {
+                            name,
+                            field: ident.clone(),
+                        });
+
+                        Ok(())
+                    } else if meta.path.is_ident("has_one") || meta.path.is_ident("has_many") {
+                        if rel.is_some() {
+                            return Err(meta.error("a field can only be one relationship"));
+                        }
+
+                        let cardinality = if meta.path.is_ident("has_one") {
+                            Cardinality::One
+                        } else {
+                            Cardinality::Many
+                        };
+
+                        rel = Some(Rel {
+                            cardinality,
+                            field: ident.clone(),
+                            links: Vec::new(),
+                            name: parse_name(&meta, &ident)?,
+                        });
+
+                        Ok(())
+                    } else if meta.path.is_ident("link") {
+                        parse_links(&meta, &mut rel_links)
+                    } else {
+                        Err(meta.error("unsupported jsonapi attribute on a field"))
+                    }
+                })?;
+            }
+
+            match rel {
+                Some(mut rel) => {
+                    rel.links = rel_links;
+                    relationships.push(rel);
+                }
+                None if !rel_links.is_empty() => {
+                    return Err(Error::new(
+                        ident.span(),
+                        "#[jsonapi(link(...))] on a field requires has_one or has_many",
+                    ));
+                }
+                None => {}
+            }
+        }
+
+        let id = match id {
+            Some(id) => id,
+            None => fields
+                .iter()
+                .filter_map(|field| field.ident.clone())
+                .find(|ident| ident == "id")
+                .ok_or_else(|| {
+                    Error::new(
+                        input.ident.span(),
+                        "missing an `id` field or a field with a #[jsonapi(id)] attribute",
+                    )
+                })?,
+        };
+
+        Ok(Container {
+            attrs,
+            fields: fields
+                .iter()
+                .filter_map(|field| field.ident.clone())
+                .collect(),
+            id,
+            kind,
+            links,
+            relationships,
+        })
+    }
+}
+
+impl Link {
+    /// Returns the names of the fields referenced by `{field}` placeholders in the
+    /// `href` template. Escaped braces (`{{` and `}}`) are ignored.
+    pub fn placeholders(&self) -> Result<Vec<Ident>> {
+        let template = self.href.value();
+        let mut chars = template.chars().peekable();
+        let mut names = Vec::<Ident>::new();
+
+        while let Some(value) = chars.next() {
+            if value != '{' {
+                continue;
+            }
+
+            if chars.peek() == Some(&'{') {
+                chars.next();
+                continue;
+            }
+
+            let mut name = String::new();
+
+            for value in chars.by_ref() {
+                match value {
+                    '}' | ':' => break,
+                    _ => name.push(value),
+                }
+            }
+
+            let ident = syn::parse_str::<Ident>(&name).map_err(|_| {
+                Error::new(
+                    self.href.span(),
+                    format!("invalid placeholder `{{{}}}` in link", name),
+                )
+            })?;
+
+            if !names.contains(&ident) {
+                names.push(ident);
+            }
+        }
+
+        Ok(names
+            .into_iter()
+            .map(|ident| Ident::new(&ident.to_string(), self.href.span()))
+            .collect())
+    }
+}
+
+fn jsonapi_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("jsonapi"))
+}
+
+/// Parses the optional `= "name"` part of an `attr`, `has_one` or `has_many` attribute.
+/// Defaults to the name of the field.
+fn parse_name(meta: &ParseNestedMeta, field: &Ident) -> Result<LitStr> {
+    let name = if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse()?
+    } else {
+        let name = field.to_string();
+        let name = name.strip_prefix("r#").unwrap_or(&name);
+
+        LitStr::new(name, field.span())
+    };
+
+    member_name(&name)
+}
+
+/// Returns `name` as a valid member name, with the same rules as the `FromStr`
+/// implementation of `json_api::value::Key`. An invalid name is reported at compile
+/// time rather than when the resource is rendered.
+fn member_name(name: &LitStr) -> Result<LitStr> {
+    let source = name.value();
+    let invalid = |reason: String| {
+        Error::new(
+            name.span(),
+            format!("invalid member name `{}`: {}", source, reason),
+        )
+    };
+
+    if source.is_empty() {
+        return Err(invalid("cannot be blank".to_owned()));
+    }
+
+    let mut dest = String::with_capacity(source.len() + 10);
+    let mut chars = source.chars().peekable();
+
+    while let Some(value) = chars.next() {
+        match value {
+            '\u{002e}'
+            | '\u{002f}'
+            | '\u{0040}'
+            | '\u{0060}'
+            | '\u{0000}'..='\u{001f}'
+            | '\u{0021}'..='\u{0029}'
+            | '\u{002a}'..='\u{002c}'
+            | '\u{003a}'..='\u{003f}'
+            | '\u{005b}'..='\u{005e}'
+            | '\u{007b}'..='\u{007f}' => {
+                return Err(invalid(format!("reserved '{}'", value)));
+            }
+            '_' | '-' | ' ' if dest.is_empty() => {
+                return Err(invalid(format!("cannot start with '{}'", value)));
+            }
+            '_' | '-' | ' ' => match chars.peek() {
+                Some(&'-') | Some(&'_') | Some(&' ') | Some(&('A'..='Z')) => {
+                    continue;
+                }
+                Some(_) => {
+                    dest.push('-');
+                }
+                None => {
+                    return Err(invalid(format!("cannot end with '{}'", value)));
+                }
+            },
+            'A'..='Z' if dest.ends_with('-') => {
+                dest.push(value.to_ascii_lowercase());
+            }
+            'A'..='Z' => {
+                dest.push('-');
+                dest.push(value.to_ascii_lowercase());
+            }
+            _ => {
+                dest.push(value);
+            }
+        }
+    }
+
+    Ok(LitStr::new(&dest, name.span()))
+}
+
+/// Parses the contents of `link(name = "href", ...)`.
+fn parse_links(meta: &ParseNestedMeta, links: &mut Vec<Link>) -> Result<()> {
+    meta.parse_nested_meta(|item| {
+        let name = item
+            .path
+            .get_ident()
+            .map(|ident| LitStr::new(&ident.to_string(), ident.span()))
+            .ok_or_else(|| item.error("expected a link name"))?;
+
+        links.push(Link {
+            name: member_name(&name)?,
+            href: item.value()?.parse()?,
+        });
+
+        Ok(())
+    })
+}
@@ -0,0 +1,403 @@
+//! Derive macros for implementing the `Resource` and `FromObject` traits of the
+//! [json-api] crate.
+//!
+//! This crate is re-exported by [json-api] when the `derive` feature is enabled. Check
+//! out the documentation of [`Resource`](derive.Resource.html) and
+//! [`FromObject`](derive.FromObject.html) for more information.
+//!
+//! [json-api]: https://docs.rs/json-api
+
+mod attr;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Result};
+
+use crate::attr::{Cardinality, Container, Link};
+
+/// Implements the `Resource` trait for a struct with named fields.
+///
+/// The generated implementation behaves like one created with the `resource!` macro,
+/// including support for sparse field-sets and included resources. Unlike the
+/// `resource!` macro, it can be used with generic structs and alongside the attributes
+/// of other derive macros, such as those of serde.
+///
+/// # Attributes
+///
+/// On the struct:
+///
+/// * `#[jsonapi(kind = "posts")]` - The type of the resource. Required.
+/// * `#[jsonapi(link(self = "/posts/{id}"))]` - Adds links to the resource object. Any
+///   `{field}` placeholder in the href is replaced with the value of the field.
+///
+/// On a field:
+///
+/// * `#[jsonapi(id)]` - Use the field as the id of the resource. Defaults to the field
+///   named `id`.
+/// * `#[jsonapi(attr)]` or `#[jsonapi(attr = "name")]` - Render the field as an
+///   attribute.
+/// * `#[jsonapi(has_one)]` or `#[jsonapi(has_one = "name")]` - Render the field as a
+///   to-one relationship. The field must be an `Option<T>` where `T: Resource`.
+/// * `#[jsonapi(has_many)]` or `#[jsonapi(has_many = "name")]` - Render the field as a
+///   to-many relationship. A reference to the field must implement `IntoIterator` with
+///   an item of `&T` where `T: Resource`.
+/// * `#[jsonapi(link(related = "/posts/{id}/comments"))]` - Adds links to a
+///   relationship.
+///
+/// The names of the type, attributes, relationships and links follow the same rules as
+/// a parsed `Key`, so `published_at` is rendered as `published-at`. A name that is not
+/// a valid member name is a compile error.
+///
+/// ```compile_fail
+/// # extern crate json_api;
+/// #
+/// use json_api::Resource;
+///
+/// #[derive(Resource)]
+/// #[jsonapi(kind = "posts")]
+/// struct Post {
+///     id: u64,
+///     #[jsonapi(attr = "title!")]
+///     title: String,
+/// }
+/// #
+/// # fn main() {}
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::Object;
+/// use json_api::Resource;
+///
+/// #[derive(Resource)]
+/// #[jsonapi(kind = "posts", link(self = "/posts/{id}"))]
+/// struct Post {
+///     id: u64,
+///     #[jsonapi(attr)]
+///     title: String,
+///     #[jsonapi(has_one)]
+///     author: Option<User>,
+///     #[jsonapi(has_many, link(related = "/posts/{id}/comments"))]
+///     comments: Vec<Comment>,
+/// }
+/// #
+/// # #[derive(Resource)]
+/// # #[jsonapi(kind = "users")]
+/// # struct User {
+/// #     id: u64,
+/// # }
+/// #
+/// # #[derive(Resource)]
+/// # #[jsonapi(kind = "comments")]
+/// # struct Comment {
+/// #     id: u64,
+/// # }
+///
+/// let post = Post {
+///     id: 1,
+///     title: "Hello, World!".to_owned(),
+///     author: Some(User { id: 2 }),
+///     comments: Vec::new(),
+/// };
+///
+/// let doc = json_api::to_doc::<_, Object>(&post, None)?;
+/// assert_eq!(doc.links().get("self").map(|link| link.to_string()), Some("/posts/1".to_owned()));
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+#[proc_macro_derive(Resource, attributes(jsonapi))]
+pub fn derive_resource(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Implements the `FromObject` trait for a struct with named fields.
+///
+/// The same `#[jsonapi(...)]` attributes accepted by `#[derive(Resource)]` are used to
+/// map the members of a resource object onto the fields of the struct. The id is
+/// parsed with `FromStr`, attributes are deserialized with serde, and relationships
+/// are resolved from the resources included in the document. Links are ignored and
+/// fields without a `#[jsonapi(...)]` attribute are set to their default value.
+///
+/// A resource object with a type other than `kind` is rejected.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// # extern crate serde_json;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::{self, Document, FromObject, Object};
+///
+/// #[derive(FromObject)]
+/// #[jsonapi(kind = "posts")]
+/// struct Post {
+///     id: u64,
+///     #[jsonapi(attr)]
+///     title: String,
+///     #[jsonapi(has_one)]
+///     author: Option<User>,
+///     #[jsonapi(has_many)]
+///     comments: Vec<Comment>,
+/// }
+/// #
+/// # #[derive(FromObject)]
+/// # #[jsonapi(kind = "users")]
+/// # struct User {
+/// #     id: u64,
+/// # }
+/// #
+/// # #[derive(FromObject)]
+/// # #[jsonapi(kind = "comments")]
+/// # struct Comment {
+/// #     id: u64,
+/// # }
+///
+/// let doc: Document<Object> = serde_json::from_str(r#"{
+///     "data": {
+///         "type": "posts",
+///         "id": "1",
+///         "attributes": { "title": "Hello, World!" },
+///         "relationships": {
+///             "author": { "data": { "type": "users", "id": "2" } },
+///             "comments": { "data": [] }
+///         }
+///     },
+///     "included": [{ "type": "users", "id": "2" }]
+/// }"#)?;
+///
+/// let post = doc::resolve_one::<Post>(&doc)?.unwrap();
+///
+/// assert_eq!(post.title, "Hello, World!");
+/// assert_eq!(post.author.map(|user| user.id), Some(2));
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+#[proc_macro_derive(FromObject, attributes(jsonapi))]
+pub fn derive_from_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_from_object(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    let container = Container::from_ast(input)?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let id = &container.id;
+    let kind = &container.kind;
+
+    let attrs = container.attrs.iter().map(|attr| {
+        let field = &attr.field;
+        let name = &attr.name;
+
+        quote! {
+            let key = ::json_api::value::Key::from_raw(#name.to_owned());
+
+            if ctx.field(&key) {
+                let value = ::json_api::to_value(&self.#field)?;
+
+                obj.attributes.insert(key, value);
+            }
+        }
+    });
+
+    let links = expand_links(&container.links, quote!(obj.links))?;
+
+    let mut relationships = Vec::with_capacity(container.relationships.len());
+    let mut schema = container
+        .attrs
+        .iter()
+        .map(|attr| {
+            let name = &attr.name;
+
+            quote! {
+                schema.attributes.insert(::json_api::value::Key::from_raw(#name.to_owned()));
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for rel in &container.relationships {
+        let field = &rel.field;
+        let name = &rel.name;
+        let links = expand_links(&rel.links, quote!(rel.links))?;
+        let render = match rel.cardinality {
+            Cardinality::One => quote! {
+                ::json_api::__private::render_has_one(ctx, &key, self.#field.as_ref())?
+            },
+            Cardinality::Many => quote! {
+                ::json_api::__private::render_has_many(ctx, &key, &self.#field)?
+            },
+        };
+
+        let related = match rel.cardinality {
+            Cardinality::One => quote!(|this| this.#field.as_ref()),
+            Cardinality::Many => quote!(|this| &this.#field),
+        };
+
+        schema.push(quote! {
+            schema.relationships.insert(
+                ::json_api::value::Key::from_raw(#name.to_owned()),
+                ::json_api::__private::related_schema::<Self, _, _, _>(#related),
+            );
+        });
+
+        relationships.push(quote! {
+            let key = ::json_api::value::Key::from_raw(#name.to_owned());
+
+            if ctx.field(&key) {
+                #[allow(unused_mut)]
+                let mut rel = #render;
+
+                #links
+                obj.relationships.insert(key, rel);
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::json_api::Resource for #ident #ty_generics #where_clause {
+            fn kind() -> ::json_api::value::Key {
+                ::json_api::value::Key::from_raw(#kind.to_owned())
+            }
+
+            fn id(&self) -> String {
+                self.#id.to_string()
+            }
+
+            fn schema() -> ::std::option::Option<::json_api::Schema> {
+                let kind = <Self as ::json_api::Resource>::kind();
+                #[allow(unused_mut)]
+                let mut schema = ::json_api::Schema::new(kind);
+
+                #(#schema)*
+                ::std::option::Option::Some(schema)
+            }
+
+            fn to_ident(
+                &self,
+                _: &mut ::json_api::view::Context,
+            ) -> ::std::result::Result<::json_api::doc::Identifier, ::json_api::Error> {
+                let kind = <Self as ::json_api::Resource>::kind();
+                let id = ::json_api::Resource::id(self);
+
+                Ok(::json_api::doc::Identifier::new(kind, id))
+            }
+
+            fn to_object(
+                &self,
+                ctx: &mut ::json_api::view::Context,
+            ) -> ::std::result::Result<::json_api::doc::Object, ::json_api::Error> {
+                let mut obj = {
+                    let kind = <Self as ::json_api::Resource>::kind();
+                    let id = ::json_api::Resource::id(self);
+
+                    ::json_api::doc::Object::new(kind, id)
+                };
+
+                #(#attrs)*
+                #links
+                #(#relationships)*
+
+                Ok(obj)
+            }
+        }
+    })
+}
+
+/// Expands `links` into statements that insert each link into the map at `target`.
+fn expand_links(links: &[Link], target: TokenStream2) -> Result<TokenStream2> {
+    let mut stmts = Vec::with_capacity(links.len());
+
+    for link in links {
+        let name = &link.name;
+        let href = &link.href;
+        let args = link.placeholders()?;
+
+        stmts.push(quote! {
+            {
+                let key = ::json_api::value::Key::from_raw(#name.to_owned());
+                let link = format!(#href, #(#args = self.#args),*)
+                    .parse::<::json_api::doc::Link>()?;
+
+                #target.insert(key, link);
+            }
+        });
+    }
+
+    Ok(quote!(#(#stmts)*))
+}
+
+fn expand_from_object(input: &DeriveInput) -> Result<TokenStream2> {
+    let container = Container::from_ast(input)?;
+    let ident = &input.ident;
+    let kind = &container.kind;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = container.fields.iter().map(|field| {
+        let attr = container.attrs.iter().find(|attr| attr.field == *field);
+        let rel = container
+            .relationships
+            .iter()
+            .find(|rel| rel.field == *field);
+
+        let value = if *field == container.id {
+            quote!(resolver.id(object)?)
+        } else if let Some(rel) = rel {
+            let name = &rel.name;
+
+            match rel.cardinality {
+                Cardinality::One => quote!(resolver.has_one(object, #name)?),
+                Cardinality::Many => quote!(resolver.has_many(object, #name)?),
+            }
+        } else if let Some(attr) = attr {
+            let name = &attr.name;
+            quote!(resolver.attribute(object, #name)?)
+        } else {
+            quote!(::std::default::Default::default())
+        };
+
+        quote!(#field: #value)
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::json_api::doc::FromObject for #ident #ty_generics #where_clause {
+            fn from_object(
+                object: &::json_api::doc::Object,
+                resolver: &::json_api::doc::Resolver,
+            ) -> ::std::result::Result<Self, ::json_api::Error> {
+                resolver.expect_kind(object, #kind)?;
+
+                Ok(#ident {
+                    #(#fields),*
+                })
+            }
+        }
+    })
+}
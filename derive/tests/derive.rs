@@ -0,0 +1,185 @@
+use std::fmt::Display;
+
+use json_api::{doc::Object, query, Error, Resource};
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Resource, Serialize)]
+#[jsonapi(kind = "users")]
+struct User {
+    #[jsonapi(id)]
+    #[serde(skip)]
+    user_id: u64,
+    #[jsonapi(attr = "name")]
+    #[serde(rename = "name")]
+    full_name: String,
+}
+
+#[derive(Resource)]
+#[jsonapi(kind = "comments", link(self = "/comments/{id}"))]
+struct Comment<Id: Display> {
+    id: Id,
+    #[jsonapi(attr)]
+    body: String,
+}
+
+#[derive(Resource)]
+#[jsonapi(kind = "posts", link(self = "/posts/{id}"))]
+struct Post {
+    id: u64,
+    #[jsonapi(attr)]
+    title: String,
+    #[jsonapi(attr)]
+    published_at: Option<String>,
+    #[jsonapi(has_one, link(related = "/posts/{id}/author"))]
+    author: Option<User>,
+    #[jsonapi(has_many = "replies")]
+    comments: Vec<Comment<u64>>,
+    #[allow(dead_code)]
+    secret: String,
+}
+
+#[derive(Resource)]
+#[jsonapi(kind = "blogs")]
+struct Blog {
+    id: u64,
+    #[jsonapi(attr)]
+    published_at: Option<String>,
+    #[jsonapi(has_many)]
+    blog_posts: Vec<Post>,
+}
+
+fn post() -> Post {
+    Post {
+        id: 1,
+        title: "Hello, World!".to_owned(),
+        published_at: None,
+        author: Some(User {
+            user_id: 2,
+            full_name: "Jane Doe".to_owned(),
+        }),
+        comments: vec![Comment {
+            id: 3,
+            body: "First!".to_owned(),
+        }],
+        secret: "hidden".to_owned(),
+    }
+}
+
+#[test]
+fn derive_resource() -> Result<(), Error> {
+    let value = serde_json::to_value(json_api::to_doc::<_, Object>(&post(), None)?)?;
+
+    assert_eq!(
+        value["data"],
+        json!({
+            "type": "posts",
+            "id": "1",
+            "attributes": {
+                "title": "Hello, World!",
+                "published-at": null,
+            },
+            "relationships": {
+                "author": {
+                    "data": { "type": "users", "id": "2" },
+                    "links": { "related": "/posts/1/author" },
+                },
+                "replies": {
+                    "data": [{ "type": "comments", "id": "3" }],
+                },
+            },
+        })
+    );
+    assert_eq!(value["links"], json!({ "self": "/posts/1" }));
+
+    Ok(())
+}
+
+#[test]
+fn derive_resource_with_query() -> Result<(), Error> {
+    let query =
+        query::from_str("fields[posts]=title,replies&fields[users]=name&include=author,replies")?;
+    let value = serde_json::to_value(json_api::to_doc::<_, Object>(&post(), Some(&query))?)?;
+
+    assert_eq!(
+        value["data"]["attributes"],
+        json!({ "title": "Hello, World!" })
+    );
+    assert!(value["data"]["relationships"].get("author").is_none());
+    assert_eq!(
+        value["included"],
+        json!([{
+            "type": "comments",
+            "id": "3",
+            "attributes": { "body": "First!" },
+            "links": { "self": "/comments/3" },
+        }])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn derive_resource_generic() {
+    let comment = Comment {
+        id: "abc",
+        body: String::new(),
+    };
+
+    assert_eq!(Comment::<&str>::kind(), *"comments");
+    assert_eq!(comment.id(), "abc");
+}
+
+#[test]
+fn derive_resource_schema() -> Result<(), Error> {
+    let schema = Post::schema().expect("expected a schema");
+    let related = schema.relationships.keys().map(|key| key.to_string());
+
+    assert_eq!(schema.kind, *"posts");
+    assert!(schema.attributes.contains("title"));
+    assert!(!schema.has_field("secret"));
+    assert_eq!(related.collect::<Vec<_>>(), vec!["author", "replies"]);
+
+    assert!(query::from_str("include=author,replies")?
+        .validate_include::<Post>()
+        .is_ok());
+
+    let error = query::from_str("include=replies.author")?
+        .validate_include::<Post>()
+        .unwrap_err();
+
+    assert_eq!(
+        error.error_source().and_then(|source| source.parameter),
+        Some("include".to_owned())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn derive_resource_schema_multi_word_names() -> Result<(), Error> {
+    assert!(query::from_str("include=blog-posts.replies")?
+        .validate_include::<Blog>()
+        .is_ok());
+
+    let query = query::from_str(
+        "fields[blogs]=published-at,blog-posts&fields[posts]=published-at&include=blog-posts",
+    )?;
+
+    assert!(query.validate_include::<Blog>().is_ok());
+    assert!(query.validate_fields::<Blog>().is_ok());
+
+    let blog = Blog {
+        id: 1,
+        published_at: None,
+        blog_posts: vec![post()],
+    };
+    let value = serde_json::to_value(json_api::to_doc::<_, Object>(&blog, Some(&query))?)?;
+
+    assert_eq!(value["data"]["attributes"], json!({ "published-at": null }));
+    assert_eq!(
+        value["included"][0]["attributes"],
+        json!({ "published-at": null })
+    );
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use json_api::{
+    doc::{self, Document, FromObject, Object},
+    Error,
+};
+use serde_json::json;
+
+#[derive(Debug, FromObject, PartialEq)]
+#[jsonapi(kind = "users")]
+struct User {
+    #[jsonapi(id)]
+    user_id: u64,
+    #[jsonapi(attr = "name")]
+    full_name: String,
+}
+
+#[derive(Debug, FromObject, PartialEq)]
+#[jsonapi(kind = "comments")]
+struct Comment {
+    id: String,
+    #[jsonapi(attr)]
+    body: String,
+}
+
+#[derive(Debug, FromObject, PartialEq)]
+#[jsonapi(kind = "posts")]
+struct Post {
+    id: u64,
+    #[jsonapi(attr)]
+    title: String,
+    #[jsonapi(attr)]
+    published_at: Option<String>,
+    #[jsonapi(has_one)]
+    author: Option<User>,
+    #[jsonapi(has_many = "replies")]
+    comments: Vec<Comment>,
+    secret: String,
+}
+
+fn document(value: serde_json::Value) -> Result<Document<Object>, Error> {
+    Ok(serde_json::from_value(value)?)
+}
+
+#[test]
+fn derive_from_object() -> Result<(), Error> {
+    let doc = document(json!({
+        "data": {
+            "type": "posts",
+            "id": "1",
+            "attributes": {
+                "title": "Hello, World!",
+                "published-at": "2017-01-01T00:00:00Z",
+            },
+            "relationships": {
+                "author": { "data": { "type": "users", "id": "2" } },
+                "replies": { "data": [{ "type": "comments", "id": "3" }] },
+            },
+        },
+        "included": [{
+            "type": "comments",
+            "id": "3",
+            "attributes": { "body": "First!" },
+        }, {
+            "type": "users",
+            "id": "2",
+            "attributes": { "name": "Jane Doe" },
+        }],
+    }))?;
+
+    assert_eq!(
+        doc::resolve_one::<Post>(&doc)?,
+        Some(Post {
+            id: 1,
+            title: "Hello, World!".to_owned(),
+            published_at: Some("2017-01-01T00:00:00Z".to_owned()),
+            author: Some(User {
+                user_id: 2,
+                full_name: "Jane Doe".to_owned(),
+            }),
+            comments: vec![Comment {
+                id: "3".to_owned(),
+                body: "First!".to_owned(),
+            }],
+            secret: String::new(),
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn derive_from_object_wrong_kind() -> Result<(), Error> {
+    let doc = document(json!({
+        "data": [{ "type": "comments", "id": "3", "attributes": { "title": "" } }],
+    }))?;
+    let e = doc::resolve_many::<Post, Vec<_>>(&doc).unwrap_err();
+
+    assert!(e.to_string().contains(r#""/data/0/type""#));
+    Ok(())
+}
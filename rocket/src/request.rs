@@ -4,7 +4,8 @@ use serde::de::DeserializeOwned;
 
 use json_api::{
     self,
-    doc::{NewObject, Object},
+    doc::{Data as JsonApiData, Document, ErrorObject, Identifier, NewObject, Object},
+    media_type::{self, MediaType},
     query::{Page, Query as JsonApiQuery, Sort},
     value::{
         collections::{map, set, Set},
@@ -19,8 +20,10 @@ use rocket::{
     request::{self, FromRequest, Request},
 };
 
-// FIXME: Is this a good  limit?
-const DATA_LIMIT: ByteUnit = ByteUnit::Mebibyte(10);
+use crate::error::Failure;
+
+/// The default limit of a request document, if the `json-api` limit is not configured.
+pub const DATA_LIMIT: ByteUnit = ByteUnit::Mebibyte(10);
 
 #[derive(Debug)]
 pub struct Create<T: DeserializeOwned>(pub T);
@@ -50,16 +53,19 @@ impl<T: DeserializeOwned> DerefMut for Create<T> {
 impl<'r, T: DeserializeOwned> FromData<'r> for Create<T> {
     type Error = Error;
 
-    async fn from_data(_: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
-        let string = match data.open(DATA_LIMIT).into_string().await {
-            Ok(string) if string.is_complete() => string.into_inner(),
-            Ok(_) => return fail(Error::payload_too_large(DATA_LIMIT.as_u64())),
-            Err(e) => return fail(e.into()),
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        if let Err(e) = negotiate(req) {
+            return fail(req, e);
+        }
+
+        let string = match read(req, data).await {
+            Ok(string) => string,
+            Err(e) => return fail(req, e),
         };
 
-        match json_api::from_str::<NewObject, _>(&string) {
+        match json_api::doc::from_body::<NewObject, _>(string.as_bytes()) {
             Ok(value) => Outcome::Success(Create(value)),
-            Err(e) => fail(e),
+            Err(e) => fail(req, e),
         }
     }
 }
@@ -92,16 +98,156 @@ impl<T: DeserializeOwned> DerefMut for Update<T> {
 impl<'r, T: DeserializeOwned> FromData<'r> for Update<T> {
     type Error = Error;
 
-    async fn from_data(_: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
-        let string = match data.open(DATA_LIMIT).into_string().await {
-            Ok(string) if string.is_complete() => string.into_inner(),
-            Ok(_) => return fail(Error::payload_too_large(DATA_LIMIT.as_u64())),
-            Err(e) => return fail(e.into()),
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        if let Err(e) = negotiate(req) {
+            return fail(req, e);
+        }
+
+        let string = match read(req, data).await {
+            Ok(string) => string,
+            Err(e) => return fail(req, e),
         };
 
-        match json_api::from_str::<Object, _>(&string) {
+        match json_api::doc::from_body::<Object, _>(string.as_bytes()) {
             Ok(value) => Outcome::Success(Update(value)),
-            Err(e) => fail(e),
+            Err(e) => fail(req, e),
+        }
+    }
+}
+
+/// A data guard for a request document that replaces the linkage of a to-one
+/// relationship, for example `PATCH /posts/1/relationships/author`. A value of `None`
+/// clears the relationship.
+#[derive(Debug)]
+pub struct UpdateToOne(pub Option<Identifier>);
+
+impl UpdateToOne {
+    /// Consumes the `UpdateToOne` wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> Option<Identifier> {
+        self.0
+    }
+}
+
+impl Deref for UpdateToOne {
+    type Target = Option<Identifier>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for UpdateToOne {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromData<'r> for UpdateToOne {
+    type Error = Error;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        if let Err(e) = negotiate(req) {
+            return fail(req, e);
+        }
+
+        let linkage = match read(req, data).await.and_then(|s| linkage(&s)) {
+            Ok(linkage) => linkage,
+            Err(e) => return fail(req, e),
+        };
+
+        match linkage {
+            JsonApiData::Member(value) => Outcome::Success(UpdateToOne(*value)),
+            JsonApiData::Collection(_) => {
+                let reason = "expected a resource identifier or null";
+                fail(req, Error::invalid_member("/data", reason))
+            }
+        }
+    }
+}
+
+/// A data guard for a request document that contains the linkage of a to-many
+/// relationship.
+///
+/// The same document is used to add members to a relationship with `POST`, remove
+/// members from a relationship with `DELETE`, and replace every member of a
+/// relationship with `PATCH`, for example `/posts/1/relationships/comments`.
+#[derive(Debug)]
+pub struct UpdateToMany(pub Vec<Identifier>);
+
+impl UpdateToMany {
+    /// Consumes the `UpdateToMany` wrapper and returns the wrapped value.
+    pub fn into_inner(self) -> Vec<Identifier> {
+        self.0
+    }
+}
+
+impl Deref for UpdateToMany {
+    type Target = Vec<Identifier>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for UpdateToMany {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromData<'r> for UpdateToMany {
+    type Error = Error;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        if let Err(e) = negotiate(req) {
+            return fail(req, e);
+        }
+
+        let linkage = match read(req, data).await.and_then(|s| linkage(&s)) {
+            Ok(linkage) => linkage,
+            Err(e) => return fail(req, e),
+        };
+
+        match linkage {
+            JsonApiData::Collection(value) => Outcome::Success(UpdateToMany(value)),
+            JsonApiData::Member(_) => {
+                let reason = "expected an array of resource identifiers";
+                fail(req, Error::invalid_member("/data", reason))
+            }
+        }
+    }
+}
+
+/// A request guard that enforces the content negotiation rules of the JSON API
+/// specification.
+///
+/// Fails with `415 Unsupported Media Type` if the `Content-Type` of the request is not
+/// the JSON API media type or has parameters other than `profile`, and with `406 Not
+/// Acceptable` if every instance of the JSON API media type in the `Accept` header has
+/// parameters other than `profile`. The [`Create`] and [`Update`] data guards apply the
+/// same rules before the request body is parsed.
+///
+/// [`Create`]: ./struct.Create.html
+/// [`Update`]: ./struct.Update.html
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Negotiate {
+    /// The media type of the request document.
+    pub content_type: MediaType,
+
+    /// The media type that the client accepts for the response document.
+    pub accept: MediaType,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Negotiate {
+    type Error = Error;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match negotiate(req) {
+            Ok(value) => Outcome::Success(value),
+            Err(e) => fail(req, e),
         }
     }
 }
@@ -132,7 +278,7 @@ impl Query {
     }
 
     pub fn page(&self) -> Option<Page> {
-        self.inner.page
+        self.inner.page.clone()
     }
 
     pub fn sort(&self) -> set::Iter<Sort> {
@@ -156,26 +302,111 @@ impl DerefMut for Query {
 
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for Query {
-    type Error = Error;
+    type Error = ErrorObject;
 
     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
-        // FIXME: It may not be necessary to go from `rocket::http::ury::Query` to `JsonApiQuery`
-        // via `&str`
-        let request_query = req.uri().query();
-        match request_query.map(|query| json_api::query::from_str(query.as_str())) {
-            Some(Ok(inner)) => Outcome::Success(Query { inner }),
-            Some(Err(e)) => fail(e),
-            None => Outcome::Success(Default::default()),
+        match cached_query(req) {
+            Ok(inner) => Outcome::Success(Query {
+                inner: inner.clone(),
+            }),
+            Err(error) => fail_with(req, error.clone(), error.clone()),
         }
     }
 }
 
-fn fail<T, F>(e: Error) -> Outcome<T, (Status, Error), F> {
-    // use config::ROCKET_ENV;
-    //
-    // if !ROCKET_ENV.is_prod() {
-    //     eprintln!("{:?}", e);
-    // }
+/// The result of parsing the query of a request, stored in the request-local state.
+/// An error is stored as the error object that describes it.
+struct CachedQuery(Result<JsonApiQuery, ErrorObject>);
+
+/// Returns the parsed query of `req`, or the error object that describes why it could
+/// not be parsed.
+///
+/// The query is parsed once and cached in the request-local state, so the [`Query`]
+/// guard and the responders of a request share the same value.
+///
+/// [`Query`]: ./struct.Query.html
+pub(crate) fn cached_query<'r>(req: &'r Request<'_>) -> Result<&'r JsonApiQuery, &'r ErrorObject> {
+    let cached =
+        req.local_cache(|| CachedQuery(parse_query(req).map_err(|e| ErrorObject::from(&e))));
+
+    cached.0.as_ref()
+}
+
+fn parse_query(req: &Request) -> Result<JsonApiQuery, Error> {
+    match req.uri().query() {
+        Some(query) => json_api::query::from_str(query.as_str()),
+        None => Ok(Default::default()),
+    }
+}
+
+fn negotiate(req: &Request) -> Result<Negotiate, Error> {
+    let headers = req.headers();
+    let negotiated = media_type::negotiate(
+        headers.get_one("Content-Type"),
+        headers.get_one("Accept"),
+        &[],
+    )?;
+
+    Ok(Negotiate {
+        content_type: negotiated.content_type,
+        accept: negotiated.accept,
+    })
+}
+
+/// Returns the limit of a request document that is sent to the route of `req`.
+///
+/// The limit is read from the `json-api/<route name>` key of the configured
+/// [`Limits`], then from the `json-api` key, and defaults to [`DATA_LIMIT`].
+///
+/// [`Limits`]: https://api.rocket.rs/v0.5/rocket/data/struct.Limits.html
+/// [`DATA_LIMIT`]: ./constant.DATA_LIMIT.html
+pub fn data_limit(req: &Request) -> ByteUnit {
+    let limits = req.limits();
+    let found = match req.route().and_then(|route| route.name.as_deref()) {
+        Some(name) => limits.find(["json-api", name]),
+        None => limits.get("json-api"),
+    };
+
+    found.unwrap_or(DATA_LIMIT)
+}
+
+/// Reads a request document, up to the limit returned by [`data_limit`].
+///
+/// [`data_limit`]: ./fn.data_limit.html
+async fn read(req: &Request<'_>, data: Data<'_>) -> Result<String, Error> {
+    let limit = data_limit(req);
+    let string = data.open(limit).into_string().await?;
+
+    if string.is_complete() {
+        Ok(string.into_inner())
+    } else {
+        Err(Error::payload_too_large(limit.as_u64()))
+    }
+}
+
+/// Returns the primary data of a request document that contains the linkage of a
+/// relationship.
+fn linkage(body: &str) -> Result<JsonApiData<Identifier>, Error> {
+    match json_api::doc::document_from_body(body.as_bytes())? {
+        Document::Ok { data, .. } => Ok(data),
+        Document::Err { .. } | Document::Meta { .. } => Err(Error::missing_member("/data")),
+    }
+}
+
+/// Stores `e` in the request-local state for the catcher to render and fails with the
+/// status code that best describes it.
+fn fail<T, F>(req: &Request, e: Error) -> Outcome<T, (Status, Error), F> {
+    fail_with(req, ErrorObject::from(&e), e)
+}
+
+/// Stores `error` in the request-local state for the catcher to render and fails with
+/// the status of `error`.
+fn fail_with<T, E, F>(req: &Request, error: ErrorObject, e: E) -> Outcome<T, (Status, E), F> {
+    let status = error
+        .status
+        .and_then(|status| Status::from_code(status.as_u16()))
+        .unwrap_or(Status::BadRequest);
 
-    Outcome::Failure((Status::BadRequest, e))
+    req.local_cache(|| Failure(Some(error)));
+    Outcome::Error((status, e))
 }
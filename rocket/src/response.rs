@@ -2,13 +2,77 @@ use std::io::Cursor;
 use std::iter::FromIterator;
 use std::ops::{Deref, DerefMut};
 
-use json_api::{self, doc::Object, Error, Resource};
+use json_api::{
+    self,
+    doc::{Document, ErrorObject, Identifier, Object},
+    value::Map,
+    view::Linkage,
+    Error, Resource,
+};
 use rocket::{
     http::Status,
     request::Request,
     response::{Responder, Response},
 };
 
+use crate::{error, request};
+
+/// Responds with `202 Accepted` to a request that has been accepted for processing,
+/// but has not been completed. The wrapped value is usually a resource that describes
+/// the job that processes the request.
+///
+/// The `Content-Location` header of the response is the `self` link of the rendered
+/// resource, if it has one.
+#[derive(Debug)]
+pub struct Accepted<T: Resource>(pub T);
+
+impl<T: Resource> Accepted<T> {
+    /// Consumes the [`Accepted`] wrapper and returns the wrapped value.
+    ///
+    /// [`Accepted`]: ./struct.Accepted.html
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Resource> Deref for Accepted<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Resource> DerefMut for Accepted<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'r, T: Resource> Responder<'r, 'static> for Accepted<T> {
+    fn respond_to(self, _: &Request) -> Result<Response<'static>, Status> {
+        let doc = match json_api::to_doc::<_, Object>(&*self, None) {
+            Ok(doc) => doc,
+            Err(e) => return fail(e),
+        };
+
+        let location = doc.links().get("self").map(|link| link.to_string());
+
+        json_api::to_vec(doc, None)
+            .map(with_body)
+            .or_else(fail)
+            .map(|mut resp| {
+                resp.set_status(Status::Accepted);
+
+                if let Some(location) = location {
+                    resp.set_raw_header("Content-Location", location);
+                }
+
+                resp
+            })
+    }
+}
+
 #[derive(Debug)]
 pub struct Collection<T: Resource>(pub Vec<T>);
 
@@ -46,14 +110,12 @@ impl<T: Resource> FromIterator<T> for Collection<T> {
 
 impl<'r, T: Resource> Responder<'r, 'static> for Collection<T> {
     fn respond_to(self, request: &'r Request<'_>) -> Result<Response<'static>, Status> {
-        let request_query = request.uri().query();
-        let query = match request_query.map(|query| json_api::query::from_str(query.as_str())) {
-            Some(Ok(query)) => Some(query),
-            Some(Err(_)) => None,
-            None => Some(Default::default()),
+        let query = match request::cached_query(request) {
+            Ok(query) => query,
+            Err(error) => return error::render(error.clone()),
         };
 
-        json_api::to_vec::<_, Object>(&*self, query.as_ref())
+        json_api::to_vec::<_, Object>(&*self, Some(query))
             .map(with_body)
             .or_else(fail)
     }
@@ -87,23 +149,82 @@ impl<T: Resource> DerefMut for Created<T> {
 
 impl<'r, T: Resource> Responder<'r, 'static> for Created<T> {
     fn respond_to(self, request: &Request) -> Result<Response<'static>, Status> {
-        let request_query = request.uri().query();
-        let query = match request_query.map(|query| json_api::query::from_str(query.as_str())) {
-            Some(Ok(query)) => Some(query),
-            Some(Err(_)) => None,
-            None => Some(Default::default()),
+        let query = match request::cached_query(request) {
+            Ok(query) => query,
+            Err(error) => return error::render(error.clone()),
+        };
+
+        let doc = match json_api::to_doc::<_, Object>(&*self, Some(query)) {
+            Ok(doc) => doc,
+            Err(e) => return fail(e),
         };
 
-        json_api::to_vec::<_, Object>(&*self, query.as_ref())
+        // The location of a new resource matches its `self` link.
+        let location = doc.links().get("self").map(|link| link.to_string());
+
+        json_api::to_vec(doc, None)
             .map(with_body)
             .or_else(fail)
             .map(|mut resp| {
                 resp.set_status(Status::Created);
+
+                if let Some(location) = location {
+                    resp.set_raw_header("Location", location);
+                }
+
                 resp
             })
     }
 }
 
+/// Responds to a successful `DELETE` request.
+///
+/// Responds with `204 No Content` if the wrapped meta information is empty. Otherwise,
+/// responds with `200 OK` and a document that only contains the meta information.
+#[derive(Debug, Default)]
+pub struct Deleted(pub Map);
+
+impl Deleted {
+    /// Consumes the [`Deleted`] wrapper and returns the wrapped value.
+    ///
+    /// [`Deleted`]: ./struct.Deleted.html
+    pub fn into_inner(self) -> Map {
+        self.0
+    }
+}
+
+impl Deref for Deleted {
+    type Target = Map;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Deleted {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Deleted {
+    fn respond_to(self, request: &'r Request<'_>) -> Result<Response<'static>, Status> {
+        if self.is_empty() {
+            return NoContent.respond_to(request);
+        }
+
+        let doc = Document::<Identifier>::Meta {
+            jsonapi: Default::default(),
+            links: Default::default(),
+            meta: self.0,
+        };
+
+        json_api::to_vec::<_, Identifier>(doc, None)
+            .map(with_body)
+            .or_else(fail)
+    }
+}
+
 #[derive(Debug)]
 pub struct Member<T>(pub T);
 
@@ -132,14 +253,114 @@ impl<T: Resource> DerefMut for Member<T> {
 
 impl<'r, T: Resource> Responder<'r, 'static> for Member<T> {
     fn respond_to(self, request: &Request) -> Result<Response<'static>, Status> {
-        let request_query = request.uri().query();
-        let query = match request_query.map(|query| json_api::query::from_str(query.as_str())) {
-            Some(Ok(query)) => Some(query),
-            Some(Err(_)) => None,
-            None => Some(Default::default()),
+        let query = match request::cached_query(request) {
+            Ok(query) => query,
+            Err(error) => return error::render(error.clone()),
         };
 
-        json_api::to_vec::<_, Object>(&*self, query.as_ref())
+        json_api::to_vec::<_, Object>(&*self, Some(query))
+            .map(with_body)
+            .or_else(fail)
+    }
+}
+
+/// Responds with `204 No Content`. Used when a request was successful and the server
+/// did not change the resource in ways that the client is not aware of.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoContent;
+
+impl<'r> Responder<'r, 'static> for NoContent {
+    fn respond_to(self, _: &'r Request<'_>) -> Result<Response<'static>, Status> {
+        Response::build().status(Status::NoContent).ok()
+    }
+}
+
+/// Renders the linkage of a to-many relationship, for example in response to
+/// `GET /posts/1/relationships/comments`.
+///
+/// The `self` and `related` links of the document are derived from the path of the
+/// request.
+#[derive(Debug)]
+pub struct ToMany<T: Resource>(pub Vec<T>);
+
+impl<T: Resource> ToMany<T> {
+    /// Consumes the [`ToMany`] wrapper and returns the wrapped value.
+    ///
+    /// [`ToMany`]: ./struct.ToMany.html
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T: Resource> Deref for ToMany<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Resource> DerefMut for ToMany<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Resource> FromIterator<T> for ToMany<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        ToMany(Vec::from_iter(iter))
+    }
+}
+
+impl<'r, T: Resource> Responder<'r, 'static> for ToMany<T> {
+    fn respond_to(self, request: &'r Request<'_>) -> Result<Response<'static>, Status> {
+        let path = request.uri().path().to_string();
+
+        json_api::to_vec::<_, Identifier>(Linkage::new(&*self, path), None)
+            .map(with_body)
+            .or_else(fail)
+    }
+}
+
+/// Renders the linkage of a to-one relationship, for example in response to
+/// `GET /posts/1/relationships/author`. A value of `None` is rendered as `null`.
+///
+/// The `self` and `related` links of the document are derived from the path of the
+/// request.
+#[derive(Debug)]
+pub struct ToOne<T: Resource>(pub Option<T>);
+
+impl<T: Resource> ToOne<T> {
+    /// Consumes the [`ToOne`] wrapper and returns the wrapped value.
+    ///
+    /// [`ToOne`]: ./struct.ToOne.html
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T: Resource> Deref for ToOne<T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Resource> DerefMut for ToOne<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'r, T: Resource> Responder<'r, 'static> for ToOne<T> {
+    fn respond_to(self, request: &'r Request<'_>) -> Result<Response<'static>, Status> {
+        let path = request.uri().path().to_string();
+
+        json_api::to_vec::<_, Identifier>(Linkage::new(self.as_ref(), path), None)
             .map(with_body)
             .or_else(fail)
     }
@@ -147,17 +368,16 @@ impl<'r, T: Resource> Responder<'r, 'static> for Member<T> {
 
 pub(crate) fn with_body(body: Vec<u8>) -> Response<'static> {
     Response::build()
-        .raw_header("Content-Type", "application/vnd.api+json")
+        .raw_header("Content-Type", json_api::media_type::MEDIA_TYPE)
         .sized_body(None, Cursor::new(body))
         .finalize()
 }
 
-pub(crate) fn fail(_e: Error) -> Result<Response<'static>, Status> {
-    // use config::ROCKET_ENV;
-    //
-    // if !ROCKET_ENV.is_prod() {
-    //     eprintln!("{:?}", e);
-    // }
-
-    Err(Status::InternalServerError)
+/// Responds with an error document that describes `e`.
+///
+/// Errors caused by the request, like an invalid query parameter, are described in
+/// detail. Other errors respond with `500 Internal Server Error` and do not expose
+/// their details.
+pub(crate) fn fail(e: Error) -> Result<Response<'static>, Status> {
+    error::render(ErrorObject::from(&e))
 }
@@ -0,0 +1,136 @@
+#[macro_use]
+extern crate rocket;
+
+use json_api_rocket::{Create, JsonApiFairing, Query};
+use rocket::{
+    data::{Limits, ToByteUnit},
+    http::{ContentType, Header, Status},
+    local::blocking::Client,
+    Build, Config, Rocket,
+};
+
+const MEDIA_TYPE: &str = "application/vnd.api+json";
+const DOCUMENT: &str = r#"{"data":{"type":"posts","attributes":{"title":"Hello"}}}"#;
+
+#[post("/posts", data = "<post>")]
+fn create(post: Create<serde_json::Value>) -> String {
+    post["title"].as_str().unwrap_or_default().to_owned()
+}
+
+#[post("/uploads", data = "<post>")]
+fn upload(post: Create<serde_json::Value>) -> String {
+    post["title"].as_str().unwrap_or_default().to_owned()
+}
+
+#[get("/fields")]
+fn fields(query: Query) -> String {
+    json_api::query::to_string(&query).unwrap()
+}
+
+fn app() -> Rocket<Build> {
+    let limits = Limits::default()
+        .limit("json-api", 32.bytes())
+        .limit("json-api/upload", 1.kibibytes());
+
+    rocket::custom(Config::figment().merge(("limits", limits)))
+        .attach(JsonApiFairing)
+        .mount("/", routes![create, fields, upload])
+}
+
+fn client() -> Client {
+    Client::tracked(app()).unwrap()
+}
+
+fn send(client: &Client, uri: &str, content_type: &str, body: &str) -> (Status, String) {
+    let resp = client
+        .post(uri.to_owned())
+        .header(Header::new("Content-Type", content_type.to_owned()))
+        .body(body)
+        .dispatch();
+
+    (resp.status(), resp.into_string().unwrap_or_default())
+}
+
+fn errors(body: &str) -> serde_json::Value {
+    serde_json::from_str::<serde_json::Value>(body).unwrap()["errors"][0].clone()
+}
+
+#[test]
+fn create_extracts_document() {
+    let client = client();
+    let (status, body) = send(&client, "/uploads", MEDIA_TYPE, DOCUMENT);
+
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body, "Hello");
+}
+
+#[test]
+fn create_rejects_invalid_document() {
+    let client = client();
+    let (status, body) = send(&client, "/posts", MEDIA_TYPE, r#"{"data":"#);
+    let error = errors(&body);
+
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(error["status"], "400");
+    assert_eq!(error["source"]["pointer"], "");
+    assert!(error["detail"].is_string());
+}
+
+#[test]
+fn create_rejects_unsupported_media_type() {
+    let client = client();
+    let value = format!("{}; charset=utf-8", MEDIA_TYPE);
+    let (status, body) = send(&client, "/posts", &value, "{}");
+
+    assert_eq!(status, Status::UnsupportedMediaType);
+    assert_eq!(errors(&body)["status"], "415");
+}
+
+#[test]
+fn create_rejects_unacceptable_media_type() {
+    let client = client();
+    let value = format!("{}; charset=utf-8", MEDIA_TYPE);
+    let resp = client
+        .post("/posts")
+        .header(ContentType::new("application", "vnd.api+json"))
+        .header(Header::new("Accept", value))
+        .body("{}")
+        .dispatch();
+
+    assert_eq!(resp.status(), Status::NotAcceptable);
+}
+
+#[test]
+fn create_enforces_data_limit() {
+    let client = client();
+    let (status, body) = send(&client, "/posts", MEDIA_TYPE, DOCUMENT);
+
+    assert_eq!(status, Status::PayloadTooLarge);
+    assert_eq!(errors(&body)["status"], "413");
+
+    // The `json-api/upload` limit overrides the `json-api` limit for the route.
+    let (status, _) = send(&client, "/uploads", MEDIA_TYPE, DOCUMENT);
+    assert_eq!(status, Status::Ok);
+}
+
+#[test]
+fn query_rejects_invalid_parameters() {
+    let client = client();
+    let resp = client.get("/fields?page[number]=abc").dispatch();
+
+    assert_eq!(resp.status(), Status::BadRequest);
+
+    let error = errors(&resp.into_string().unwrap());
+
+    assert_eq!(error["source"]["parameter"], "page[number]");
+    assert!(error["detail"].is_string());
+}
+
+#[test]
+fn query_extracts_parameters() {
+    let client = client();
+    let resp = client.get("/fields?fields[posts]=title").dispatch();
+
+    assert_eq!(resp.status(), Status::Ok);
+    assert_eq!(resp.into_string().unwrap(), "fields[posts]=title");
+}
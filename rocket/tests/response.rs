@@ -0,0 +1,184 @@
+#[macro_use]
+extern crate json_api;
+#[macro_use]
+extern crate rocket;
+
+use json_api::value::Map;
+use json_api_rocket::{Collection, Created, Deleted, JsonApiFairing, Member, ToMany, ToOne};
+use rocket::{
+    http::{Header, Status},
+    local::blocking::Client,
+};
+use serde_json::json;
+
+struct Post {
+    id: u64,
+    body: String,
+    title: String,
+}
+
+resource!(Post, |&self| {
+    kind "posts";
+    id self.id;
+    attrs body, title;
+    link "self", format!("/posts/{}", self.id);
+});
+
+fn post(id: u64) -> Post {
+    Post {
+        id,
+        body: "Body".to_owned(),
+        title: "Title".to_owned(),
+    }
+}
+
+#[get("/posts")]
+fn index() -> Collection<Post> {
+    Collection(vec![post(1), post(2)])
+}
+
+#[get("/posts/1")]
+fn show() -> Member<Post> {
+    Member(post(1))
+}
+
+#[post("/posts")]
+fn store() -> Created<Post> {
+    Created(post(3))
+}
+
+#[delete("/posts/<id>")]
+fn destroy(id: u64) -> Deleted {
+    let mut meta = Map::new();
+
+    if id == 2 {
+        meta.insert("archived".parse().unwrap(), true.into());
+    }
+
+    Deleted(meta)
+}
+
+#[get("/posts/1/relationships/comments")]
+fn comments() -> ToMany<Post> {
+    ToMany(vec![post(2)])
+}
+
+#[get("/posts/1/relationships/author")]
+fn author() -> ToOne<Post> {
+    ToOne(None)
+}
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .attach(JsonApiFairing)
+        .mount("/", routes![index, show, store, destroy, comments, author]);
+
+    Client::tracked(rocket).unwrap()
+}
+
+fn json(body: Option<String>) -> serde_json::Value {
+    serde_json::from_str(&body.unwrap()).unwrap()
+}
+
+fn get_json(client: &Client, uri: &str) -> (Status, serde_json::Value) {
+    let resp = client.get(uri.to_owned()).dispatch();
+    let status = resp.status();
+
+    assert_eq!(
+        resp.headers().get_one("Content-Type"),
+        Some("application/vnd.api+json")
+    );
+
+    (status, json(resp.into_string()))
+}
+
+#[test]
+fn member_with_sparse_fieldset() {
+    let (status, body) = get_json(&client(), "/posts/1?fields[posts]=title");
+
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["attributes"], json!({ "title": "Title" }));
+    assert_eq!(body["links"]["self"], "/posts/1");
+}
+
+#[test]
+fn member_rejects_invalid_include() {
+    let (status, body) = get_json(&client(), "/posts/1?include=author..name");
+
+    assert_eq!(status, Status::BadRequest);
+    assert_eq!(body["errors"][0]["status"], "400");
+    assert!(body.get("data").is_none());
+}
+
+#[test]
+fn collection() {
+    let (status, body) = get_json(&client(), "/posts");
+
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"].as_array().map(Vec::len), Some(2));
+}
+
+#[test]
+fn created_sets_location() {
+    let client = client();
+    let resp = client.post("/posts").dispatch();
+
+    assert_eq!(resp.status(), Status::Created);
+    assert_eq!(resp.headers().get_one("Location"), Some("/posts/3"));
+}
+
+#[test]
+fn deleted_without_meta() {
+    let client = client();
+    let resp = client.delete("/posts/1").dispatch();
+
+    assert_eq!(resp.status(), Status::NoContent);
+    assert!(resp.into_string().is_none());
+}
+
+#[test]
+fn deleted_with_meta() {
+    let client = client();
+    let resp = client.delete("/posts/2").dispatch();
+
+    assert_eq!(resp.status(), Status::Ok);
+
+    let body = json(resp.into_string());
+
+    assert_eq!(body["meta"], json!({ "archived": true }));
+    assert_eq!(body["jsonapi"]["version"], "1.0");
+    assert!(body.get("data").is_none());
+}
+
+#[test]
+fn unknown_route_renders_error_document() {
+    let client = client();
+    let resp = client
+        .get("/comments")
+        .header(Header::new("Accept", "application/vnd.api+json"))
+        .dispatch();
+
+    assert_eq!(resp.status(), Status::NotFound);
+    assert_eq!(json(resp.into_string())["errors"][0]["status"], "404");
+}
+
+#[test]
+fn relationship_links() {
+    let client = client();
+    let (_, body) = get_json(&client, "/posts/1/relationships/comments");
+
+    assert_eq!(body["data"], json!([{ "id": "2", "type": "posts" }]));
+    assert_eq!(
+        body["links"],
+        json!({
+            "self": "/posts/1/relationships/comments",
+            "related": "/posts/1/comments",
+        })
+    );
+
+    let (_, body) = get_json(&client, "/posts/1/relationships/author");
+
+    assert_eq!(body["data"], serde_json::Value::Null);
+    assert_eq!(body["links"]["self"], "/posts/1/relationships/author");
+    assert_eq!(body["links"]["related"], "/posts/1/author");
+}
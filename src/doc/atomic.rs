@@ -0,0 +1,631 @@
+//! Documents of the official *[Atomic Operations]* extension.
+//!
+//! An `atomic:operations` document contains a list of operations that a server must
+//! perform in order and atomically. The response to a successful request is an
+//! `atomic:results` document, containing one result for each operation.
+//!
+//! # Example
+//!
+//! ```
+//! # extern crate json_api;
+//! #
+//! # use json_api::Error;
+//! #
+//! # fn example() -> Result<(), Error> {
+//! use json_api::doc::atomic::{self, AtomicDocument, OpCode, OperationData};
+//!
+//! let doc: AtomicDocument = atomic::from_str(r#"{
+//!     "atomic:operations": [{
+//!         "op": "add",
+//!         "data": {
+//!             "type": "articles",
+//!             "attributes": { "title": "JSON API paints my bikeshed!" }
+//!         }
+//!     }, {
+//!         "op": "remove",
+//!         "ref": { "type": "articles", "id": "13" }
+//!     }]
+//! }"#)?;
+//!
+//! let operations = doc.operations().unwrap();
+//!
+//! assert_eq!(operations[0].op, OpCode::Add);
+//! assert!(matches!(operations[0].data, Some(OperationData::New(_))));
+//! assert_eq!(operations[1].op, OpCode::Remove);
+//! assert!(operations[1].data.is_none());
+//! #
+//! # Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! # example().unwrap();
+//! # }
+//! ```
+//!
+//! [Atomic Operations]: https://jsonapi.org/ext/atomic/
+
+use std::{convert::TryFrom, io::Read};
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::{
+    doc::{
+        Data, ErrorObject, Flattener, Identifier, JsonApi, Link, NewObject, Object, PrimaryData,
+        Relationship, Version, DEFAULT_MAX_DEPTH,
+    },
+    error::{Error, ErrorKind},
+    value::{self, Key, Map, Set, Value},
+};
+
+/// The URI of the Atomic Operations extension. Documents that are created with the
+/// constructor methods of [`AtomicDocument`] list it in the `ext` member of their
+/// JSON API object.
+///
+/// [`AtomicDocument`]: ./enum.AtomicDocument.html
+pub const EXT_URI: &str = "https://jsonapi.org/ext/atomic";
+
+/// Represents a document of the Atomic Operations extension.
+///
+/// For more information, check out the *[Atomic Operations]* extension of the JSON API
+/// specification.
+///
+/// The variant of a deserialized document is decided by its `atomic:operations`,
+/// `atomic:results` or `errors` member. An operation that can not be interpreted is
+/// reported with a pointer to the operation, such as `/atomic:operations/1/data`.
+///
+/// [Atomic Operations]: https://jsonapi.org/ext/atomic/
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum AtomicDocument {
+    /// Contains the operations of a request.
+    Operations {
+        /// The operations to perform, in order.
+        #[serde(rename = "atomic:operations")]
+        operations: Vec<Operation>,
+
+        #[serde(default)]
+        jsonapi: JsonApi,
+
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        links: Map<Key, Link>,
+
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        meta: Map,
+    },
+
+    /// Contains the results of a successful request.
+    Results {
+        /// The result of each operation, in the same order as the operations of the
+        /// request.
+        #[serde(rename = "atomic:results")]
+        results: Vec<OperationResult>,
+
+        #[serde(default)]
+        jsonapi: JsonApi,
+
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        links: Map<Key, Link>,
+
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        meta: Map,
+    },
+
+    /// Contains 1 or more error(s).
+    Err {
+        errors: Vec<ErrorObject>,
+
+        #[serde(default)]
+        jsonapi: JsonApi,
+
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        links: Map<Key, Link>,
+
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        meta: Map,
+    },
+}
+
+impl AtomicDocument {
+    /// Returns a new `atomic:operations` document containing `operations`.
+    pub fn from_operations(operations: Vec<Operation>) -> Self {
+        AtomicDocument::Operations {
+            operations,
+            jsonapi: jsonapi(),
+            links: Default::default(),
+            meta: Default::default(),
+        }
+    }
+
+    /// Returns a new `atomic:results` document containing `results`.
+    pub fn from_results(results: Vec<OperationResult>) -> Self {
+        AtomicDocument::Results {
+            results,
+            jsonapi: jsonapi(),
+            links: Default::default(),
+            meta: Default::default(),
+        }
+    }
+
+    /// Returns the operations of the document if it is an `atomic:operations`
+    /// document.
+    pub fn operations(&self) -> Option<&[Operation]> {
+        match *self {
+            AtomicDocument::Operations { ref operations, .. } => Some(operations),
+            _ => None,
+        }
+    }
+
+    /// Returns the results of the document if it is an `atomic:results` document.
+    pub fn results(&self) -> Option<&[OperationResult]> {
+        match *self {
+            AtomicDocument::Results { ref results, .. } => Some(results),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AtomicDocument {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error as DeError;
+
+        let value = JsonValue::deserialize(deserializer)?;
+        from_json(value).map_err(D::Error::custom)
+    }
+}
+
+/// The kind of an operation.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OpCode {
+    /// Creates a resource or adds members to a to-many relationship.
+    Add,
+
+    /// Updates a resource or replaces the linkage of a relationship.
+    Update,
+
+    /// Deletes a resource or removes members from a to-many relationship.
+    Remove,
+}
+
+/// A single operation of an `atomic:operations` document.
+///
+/// The type of [`data`] is decided by the operation code and target of the operation
+/// when it is deserialized:
+///
+/// * Operations that target a relationship contain resource linkage.
+/// * An `add` operation that targets a resource contains a [`NewObject`].
+/// * An `update` operation that targets a resource contains an [`Object`].
+/// * A `remove` operation that targets a resource does not contain data.
+///
+/// If an operation is targeted with `href`, it is considered to target a relationship
+/// when its data is an array or `null`, or when the path of `href` contains a
+/// `relationships` segment.
+///
+/// [`data`]: #structfield.data
+/// [`NewObject`]: ../struct.NewObject.html
+/// [`Object`]: ../struct.Object.html
+#[derive(Clone, Debug, Serialize)]
+pub struct Operation {
+    /// The data of the operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<OperationData>,
+
+    /// A URI that identifies the target of the operation. Mutually exclusive with
+    /// [`target`].
+    ///
+    /// [`target`]: #structfield.target
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub href: Option<Link>,
+
+    /// Non-standard meta information. If this value of this field is empty, it will not
+    /// be serialized. For more information, check out the *[meta information]* section
+    /// of the JSON API specification.
+    ///
+    /// [meta information]: https://goo.gl/LyrGF8
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub meta: Map,
+
+    /// The kind of the operation.
+    pub op: OpCode,
+
+    /// Identifies the target of the operation. This field is derived from the `ref`
+    /// member if the operation is deserialized.
+    #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub target: Option<Target>,
+}
+
+impl Operation {
+    /// Returns a new `Operation` with the specified `op` and `data`.
+    pub fn new(op: OpCode, data: Option<OperationData>) -> Self {
+        Operation {
+            op,
+            data,
+            href: None,
+            meta: Default::default(),
+            target: None,
+        }
+    }
+
+    /// Returns `true` if the operation targets a relationship.
+    pub fn is_relationship(&self) -> bool {
+        match self.target {
+            Some(ref target) => target.relationship.is_some(),
+            None => self.href.as_ref().is_some_and(is_relationship_href),
+        }
+    }
+
+    /// Returns the data of the operation as a relationship if the operation targets a
+    /// relationship.
+    pub fn relationship(&self) -> Option<Relationship> {
+        match self.data {
+            Some(OperationData::Linkage(ref data)) => Some(Relationship::new(data.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Operation {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error as DeError;
+
+        let raw = RawOperation::deserialize(deserializer)?;
+        Operation::try_from(raw).map_err(D::Error::custom)
+    }
+}
+
+impl TryFrom<RawOperation> for Operation {
+    type Error = Error;
+
+    fn try_from(raw: RawOperation) -> Result<Self, Self::Error> {
+        let RawOperation {
+            data,
+            href,
+            meta,
+            op,
+            target,
+        } = raw;
+
+        if target.is_some() && href.is_some() {
+            return Err(Error::invalid_member(
+                "",
+                r#"an operation cannot contain both "ref" and "href""#,
+            ));
+        }
+
+        let linkage = match (&target, &href, &data) {
+            (Some(target), _, _) => target.relationship.is_some(),
+            (None, Some(href), Some(data)) => {
+                is_relationship_href(href) || data.is_array() || data.is_null()
+            }
+            (None, Some(href), None) => is_relationship_href(href),
+            (None, None, _) => false,
+        };
+
+        let data = match (op, data) {
+            (_, Some(value)) if linkage => Some(OperationData::Linkage(operation_data(value)?)),
+            (OpCode::Add, Some(value)) => Some(OperationData::New(operation_data(value)?)),
+            (OpCode::Update, Some(value)) => Some(OperationData::Object(operation_data(value)?)),
+            (OpCode::Remove, Some(_)) => {
+                return Err(Error::invalid_member(
+                    "/data",
+                    "an operation that removes a resource cannot contain data",
+                ))
+            }
+            (_, None) => None,
+        };
+
+        Ok(Operation {
+            data,
+            href,
+            meta,
+            op,
+            target,
+        })
+    }
+}
+
+/// The data of an [`Operation`].
+///
+/// [`Operation`]: ./struct.Operation.html
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum OperationData {
+    /// A resource to create.
+    New(NewObject),
+
+    /// A resource to update.
+    Object(Object),
+
+    /// Resource linkage of a relationship.
+    Linkage(Data<Identifier>),
+}
+
+/// Identifies the resource or relationship targeted by an [`Operation`].
+///
+/// [`Operation`]: ./struct.Operation.html
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Target {
+    /// The id of the targeted resource.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// The type of the targeted resource. This field is derived from the `type` field
+    /// if the target is deserialized.
+    #[serde(rename = "type")]
+    pub kind: Key,
+
+    /// The local id of a resource that was created by a previous operation in the same
+    /// request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lid: Option<String>,
+
+    /// The name of the targeted relationship. If this value is `None`, the operation
+    /// targets the resource itself.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationship: Option<Key>,
+}
+
+impl Target {
+    /// Returns a new `Target` that identifies the resource with the specified `kind`
+    /// and `id`.
+    pub fn new(kind: Key, id: String) -> Self {
+        Target {
+            kind,
+            id: Some(id),
+            lid: None,
+            relationship: None,
+        }
+    }
+}
+
+impl From<Identifier> for Target {
+    fn from(ident: Identifier) -> Self {
+        let Identifier { id, kind, lid, .. } = ident;
+
+        Target {
+            id,
+            kind,
+            lid,
+            relationship: None,
+        }
+    }
+}
+
+/// The result of a single [`Operation`].
+///
+/// [`Operation`]: ./struct.Operation.html
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct OperationResult {
+    /// The resource that was created or updated by the operation, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Object>,
+
+    /// Non-standard meta information. If this value of this field is empty, it will not
+    /// be serialized. For more information, check out the *[meta information]* section
+    /// of the JSON API specification.
+    ///
+    /// [meta information]: https://goo.gl/LyrGF8
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub meta: Map,
+}
+
+impl OperationResult {
+    /// Returns a new `OperationResult` with the specified `data`.
+    pub fn new(data: Option<Object>) -> Self {
+        OperationResult {
+            data,
+            meta: Default::default(),
+        }
+    }
+}
+
+impl From<Object> for OperationResult {
+    fn from(data: Object) -> Self {
+        OperationResult::new(Some(data))
+    }
+}
+
+#[derive(Deserialize)]
+struct RawOperation {
+    #[serde(default, deserialize_with = "present")]
+    data: Option<Value>,
+    #[serde(default)]
+    href: Option<Link>,
+    #[serde(default)]
+    meta: Map,
+    op: OpCode,
+    #[serde(default, rename = "ref")]
+    target: Option<Target>,
+}
+
+/// Deserialize a member that may be `null` as `Some(Value::Null)`, so that it can be
+/// distinguished from a member that is absent.
+fn present<'de, D>(deserializer: D) -> Result<Option<Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Value::deserialize(deserializer).map(Some)
+}
+
+/// Interpret the data member of an operation as a type `T`.
+fn operation_data<T: DeserializeOwned>(value: Value) -> Result<T, Error> {
+    value::from_value(value).map_err(|e| Error::invalid_member("/data", &e.to_string()))
+}
+
+/// Prefix the pointer of an error that occurred while an operation was interpreted
+/// with `prefix`, the pointer of the operation.
+fn at_pointer(prefix: &str, e: Error) -> Error {
+    match *e.kind() {
+        ErrorKind::InvalidMember(ref pointer, ref reason) => {
+            Error::invalid_member(&format!("{}{}", prefix, pointer), reason)
+        }
+        ErrorKind::MissingMember(ref pointer) => {
+            Error::missing_member(&format!("{}{}", prefix, pointer))
+        }
+        _ => Error::invalid_member(prefix, &e.to_string()),
+    }
+}
+
+/// Interpret `value` as an `AtomicDocument`, choosing the variant by the members of
+/// the top-level object.
+fn from_json(value: JsonValue) -> Result<AtomicDocument, Error> {
+    #[derive(Deserialize)]
+    struct Members {
+        #[serde(default)]
+        jsonapi: JsonApi,
+        #[serde(default)]
+        links: Map<Key, Link>,
+        #[serde(default)]
+        meta: Map,
+    }
+
+    let mut object = match value {
+        JsonValue::Object(object) => object,
+        _ => return Err(Error::invalid_member("", "a document must be an object")),
+    };
+
+    if let Some(value) = object.remove("atomic:operations") {
+        let items = match value {
+            JsonValue::Array(items) => items,
+            _ => {
+                return Err(Error::invalid_member(
+                    "/atomic:operations",
+                    "expected an array of operations",
+                ))
+            }
+        };
+
+        let operations = items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| {
+                serde_json::from_value::<RawOperation>(item)
+                    .map_err(Error::from)
+                    .and_then(Operation::try_from)
+                    .map_err(|e| at_pointer(&format!("/atomic:operations/{}", index), e))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let Members {
+            jsonapi,
+            links,
+            meta,
+        } = serde_json::from_value(JsonValue::Object(object))?;
+
+        return Ok(AtomicDocument::Operations {
+            operations,
+            jsonapi,
+            links,
+            meta,
+        });
+    }
+
+    if let Some(value) = object.remove("atomic:results") {
+        let results = serde_json::from_value(value)
+            .map_err(|e| Error::invalid_member("/atomic:results", &e.to_string()))?;
+
+        let Members {
+            jsonapi,
+            links,
+            meta,
+        } = serde_json::from_value(JsonValue::Object(object))?;
+
+        return Ok(AtomicDocument::Results {
+            results,
+            jsonapi,
+            links,
+            meta,
+        });
+    }
+
+    if let Some(value) = object.remove("errors") {
+        let errors = serde_json::from_value(value)
+            .map_err(|e| Error::invalid_member("/errors", &e.to_string()))?;
+
+        let Members {
+            jsonapi,
+            links,
+            meta,
+        } = serde_json::from_value(JsonValue::Object(object))?;
+
+        return Ok(AtomicDocument::Err {
+            errors,
+            jsonapi,
+            links,
+            meta,
+        });
+    }
+
+    Err(Error::invalid_member(
+        "",
+        r#"a document must contain "atomic:operations", "atomic:results" or "errors""#,
+    ))
+}
+
+fn is_relationship_href(href: &Link) -> bool {
+    href.path()
+        .split('/')
+        .any(|segment| segment == "relationships")
+}
+
+fn jsonapi() -> JsonApi {
+    let mut jsonapi = JsonApi::new(Version::V1_1);
+
+    jsonapi.ext.insert(Link {
+        href: http::Uri::from_static(EXT_URI),
+        ..Default::default()
+    });
+
+    jsonapi
+}
+
+/// Interpret the data of an `Operation` as a type `U`.
+///
+/// Resource linkage is interpreted as the id(s) of the related resource(s).
+pub fn from_operation<U>(operation: Operation) -> Result<U, Error>
+where
+    U: DeserializeOwned,
+{
+    let included = Set::new();
+    let mut ctx = Flattener::new(&included, DEFAULT_MAX_DEPTH);
+    let value = match operation.data {
+        Some(OperationData::New(data)) => data.flatten(&mut ctx)?,
+        Some(OperationData::Object(data)) => data.flatten(&mut ctx)?,
+        Some(OperationData::Linkage(Data::Member(data))) => match *data {
+            Some(item) => item.flatten_local(&mut ctx)?,
+            None => Value::Null,
+        },
+        Some(OperationData::Linkage(Data::Collection(data))) => data
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| ctx.at(format!("/data/{}", index), |ctx| item.flatten_local(ctx)))
+            .collect::<Result<_, _>>()?,
+        None => Value::Null,
+    };
+
+    value::from_value(value)
+}
+
+/// Deserialize an `AtomicDocument` from an IO stream of JSON text.
+pub fn from_reader<R: Read>(data: R) -> Result<AtomicDocument, Error> {
+    from_json(serde_json::from_reader(data)?)
+}
+
+/// Deserialize an `AtomicDocument` from bytes of JSON text.
+pub fn from_slice(data: &[u8]) -> Result<AtomicDocument, Error> {
+    from_json(serde_json::from_slice(data)?)
+}
+
+/// Deserialize an `AtomicDocument` from a string of JSON text.
+pub fn from_str(data: &str) -> Result<AtomicDocument, Error> {
+    from_json(serde_json::from_str(data)?)
+}
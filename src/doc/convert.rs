@@ -4,30 +4,52 @@ use serde::de::DeserializeOwned;
 use serde_json;
 
 use crate::{
-    doc::{Data, Document, PrimaryData},
-    error::Error,
+    doc::{Data, Document, ErrorObject, Flattener, Object, PrimaryData, DEFAULT_MAX_DEPTH},
+    error::{Error, ErrorKind},
     query::Query,
     value::{self, Value},
     view::Render,
 };
 
 /// Interpret a `Document<T>` as a type `U`.
+///
+/// Relationships are replaced with the included resources they reference. A resource
+/// that references itself, directly or through other resources, is replaced with its
+/// id when it is revisited. Returns an error if the relationships are nested deeper
+/// than [`DEFAULT_MAX_DEPTH`]. Use [`from_doc_with_depth`] to configure this limit.
+///
+/// [`DEFAULT_MAX_DEPTH`]: crate::doc::DEFAULT_MAX_DEPTH
+/// [`from_doc_with_depth`]: crate::doc::from_doc_with_depth
 pub fn from_doc<T, U>(doc: Document<T>) -> Result<U, Error>
+where
+    T: PrimaryData,
+    U: DeserializeOwned,
+{
+    from_doc_with_depth(doc, DEFAULT_MAX_DEPTH)
+}
+
+/// Interpret a `Document<T>` as a type `U`, following at most `max_depth`
+/// relationships from the primary data.
+pub fn from_doc_with_depth<T, U>(doc: Document<T>, max_depth: usize) -> Result<U, Error>
 where
     T: PrimaryData,
     U: DeserializeOwned,
 {
     match doc {
         Document::Ok { data, included, .. } => {
+            let mut ctx = Flattener::new(&included, max_depth);
             let value = value::convert::to_json(match data {
                 Data::Member(data) => match *data {
-                    Some(item) => item.flatten(&included),
+                    Some(item) => item.flatten(&mut ctx)?,
                     None => Value::Null,
                 },
                 Data::Collection(data) => data
                     .into_iter()
-                    .map(|item| item.flatten(&included))
-                    .collect(),
+                    .enumerate()
+                    .map(|(index, item)| {
+                        ctx.at(format!("/data/{}", index), |ctx| item.flatten(ctx))
+                    })
+                    .collect::<Result<_, _>>()?,
             });
 
             Ok(serde_json::from_value(value)?)
@@ -36,6 +58,7 @@ where
             let e = Error::from("Document contains one or more error(s)");
             Err(e)
         }
+        Document::Meta { .. } => Err(Error::missing_member("/data")),
     }
 }
 
@@ -70,13 +93,86 @@ where
     from_doc::<T, _>(serde_json::from_str(data)?)
 }
 
+/// Deserialize a request document from bytes of JSON text.
+///
+/// A body that is not valid JSON, or is not a valid `Document<T>`, is reported as an
+/// invalid member at the root of the document, so it maps to `400 Bad Request` rather
+/// than a server error.
+pub fn document_from_body<T: PrimaryData>(data: &[u8]) -> Result<Document<T>, Error> {
+    serde_json::from_slice(data).map_err(|e| body_error(e.into()))
+}
+
+/// Deserialize a request document from bytes of JSON text and then iterpret it as a
+/// type `U`.
+///
+/// Errors are reported like [`document_from_body`], which makes this function suitable
+/// for the request body extractors of a web framework.
+///
+/// [`document_from_body`]: ./fn.document_from_body.html
+pub fn from_body<T, U>(data: &[u8]) -> Result<U, Error>
+where
+    T: PrimaryData,
+    U: DeserializeOwned,
+{
+    from_slice::<T, U>(data).map_err(body_error)
+}
+
+/// Interprets an error that occurred while a request document was deserialized. A
+/// document that is not valid JSON is an error of the request, rather than the server.
+fn body_error(e: Error) -> Error {
+    match *e.kind() {
+        ErrorKind::Json(ref inner) => Error::invalid_member("", &inner.to_string()),
+        _ => e,
+    }
+}
+
+/// Deserialize a `Document<T>` from an IO stream of JSON text and then
+/// iterpret it as a type `U`, following at most `max_depth` relationships.
+pub fn from_reader_with_depth<R, T, U>(data: R, max_depth: usize) -> Result<U, Error>
+where
+    R: Read,
+    T: PrimaryData,
+    U: DeserializeOwned,
+{
+    from_doc_with_depth::<T, _>(serde_json::from_reader(data)?, max_depth)
+}
+
+/// Deserialize a `Document<T>` from bytes of JSON text and then iterpret it as
+/// a type `U`, following at most `max_depth` relationships.
+pub fn from_slice_with_depth<T, U>(data: &[u8], max_depth: usize) -> Result<U, Error>
+where
+    T: PrimaryData,
+    U: DeserializeOwned,
+{
+    from_doc_with_depth::<T, _>(serde_json::from_slice(data)?, max_depth)
+}
+
+/// Deserialize a `Document<T>` from a string of JSON text and then iterpret it
+/// as a type `U`, following at most `max_depth` relationships.
+pub fn from_str_with_depth<T, U>(data: &str, max_depth: usize) -> Result<U, Error>
+where
+    T: PrimaryData,
+    U: DeserializeOwned,
+{
+    from_doc_with_depth::<T, _>(serde_json::from_str(data)?, max_depth)
+}
+
 /// Render type `T` as a `Document<U>`.
+///
+/// The version advertised by the [JSON API object] of the returned document is raised
+/// to the lowest version that can represent it.
+///
+/// [JSON API object]: https://goo.gl/hZUcEt
 pub fn to_doc<T, U>(value: T, query: Option<&Query>) -> Result<Document<U>, Error>
 where
     T: Render<U>,
     U: PrimaryData,
 {
-    value.render(query)
+    let mut doc = value.render(query)?;
+    let jsonapi = doc.jsonapi_mut();
+
+    jsonapi.version = jsonapi.required_version();
+    Ok(doc)
 }
 
 /// Render type `T` as a `Document<U>` and then serialize it as a string of
@@ -109,6 +205,25 @@ where
     Ok(serde_json::to_vec(&to_doc(value, query)?)?)
 }
 
+/// Serialize a document that only contains `error` as a JSON byte vector.
+///
+/// The status of the response that contains the document is the status of `error`.
+/// Use [`ErrorObject::from`] to describe an [`Error`] without exposing the details of
+/// errors caused by the server.
+///
+/// [`ErrorObject::from`]: ./struct.ErrorObject.html
+/// [`Error`]: ../error/struct.Error.html
+pub fn error_to_vec(error: ErrorObject) -> Result<Vec<u8>, Error> {
+    let doc: Document<Object> = Document::Err {
+        errors: vec![error],
+        jsonapi: Default::default(),
+        links: Default::default(),
+        meta: Default::default(),
+    };
+
+    to_vec(doc, None)
+}
+
 /// Render type `T` as a `Document<U>` and then serialize it as a
 /// pretty-printed JSON byte vector.
 pub fn to_vec_pretty<T, U>(value: T, query: Option<&Query>) -> Result<Vec<u8>, Error>
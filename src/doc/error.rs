@@ -91,6 +91,49 @@ impl ErrorSource {
     pub fn new(parameter: Option<String>, pointer: Option<String>) -> Self {
         ErrorSource { parameter, pointer }
     }
+
+    /// Returns a new `ErrorSource` with a pointer to the operation at `index` of an
+    /// `atomic:operations` document. If `member` is `Some`, the pointer references the
+    /// member of the operation at the specified path instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # fn main() {
+    /// use json_api::doc::ErrorSource;
+    ///
+    /// let source = ErrorSource::operation(2, Some("data"));
+    /// assert_eq!(source.pointer, Some("/atomic:operations/2/data".to_owned()));
+    /// # }
+    /// ```
+    pub fn operation(index: usize, member: Option<&str>) -> Self {
+        let pointer = match member {
+            Some(path) => format!("/atomic:operations/{}/{}", index, path),
+            None => format!("/atomic:operations/{}", index),
+        };
+
+        ErrorSource::new(None, Some(pointer))
+    }
+
+    /// Returns a new `ErrorSource` that references the query parameter `name`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # fn main() {
+    /// use json_api::doc::ErrorSource;
+    ///
+    /// let source = ErrorSource::parameter("filter[age][gt]");
+    /// assert_eq!(source.parameter, Some("filter[age][gt]".to_owned()));
+    /// # }
+    /// ```
+    pub fn parameter(name: &str) -> Self {
+        ErrorSource::new(Some(name.to_owned()), None)
+    }
 }
 
 mod serde_status {
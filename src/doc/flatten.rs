@@ -0,0 +1,143 @@
+use std::mem;
+
+use crate::{
+    doc::{Data, Identifier, Object, PrimaryData},
+    error::Error,
+    value::{Key, Set, Value},
+};
+
+/// The maximum number of relationships that are followed from the primary data when a
+/// document is interpreted with [`from_doc`].
+///
+/// [`from_doc`]: crate::from_doc
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+/// Tracks the resources that are currently being flattened so cyclic relationships
+/// between included resources terminate, and the JSON pointer of the value that is
+/// being flattened so errors reference it.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Flattener<'a> {
+    incl: &'a Set<Object>,
+    max_depth: usize,
+    pointer: String,
+    stack: Vec<Identifier>,
+}
+
+impl<'a> Flattener<'a> {
+    pub(crate) fn new(incl: &'a Set<Object>, max_depth: usize) -> Self {
+        Flattener {
+            incl,
+            max_depth,
+            pointer: "/data".to_owned(),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Returns the JSON pointer of the value that is being flattened.
+    pub(crate) fn pointer(&self) -> &str {
+        &self.pointer
+    }
+
+    /// Calls `f` with `pointer` as the pointer of the value that is being flattened.
+    pub(crate) fn at<T, F>(&mut self, pointer: String, f: F) -> Result<T, Error>
+    where
+        F: FnOnce(&mut Self) -> Result<T, Error>,
+    {
+        let parent = mem::replace(&mut self.pointer, pointer);
+        let result = f(self);
+
+        self.pointer = parent;
+        result
+    }
+
+    /// Flattens the resource linkage `data` of the relationship `key` of the resource
+    /// that is being flattened with `f`.
+    pub(crate) fn linkage<F>(
+        &mut self,
+        key: &Key,
+        data: Data<Identifier>,
+        mut f: F,
+    ) -> Result<Value, Error>
+    where
+        F: FnMut(Identifier, &mut Self) -> Result<Value, Error>,
+    {
+        let pointer = format!("{}/relationships/{}/data", self.pointer, key);
+
+        match data {
+            Data::Member(data) => match *data {
+                Some(item) => self.at(pointer, |ctx| f(item, ctx)),
+                None => Ok(Value::Null),
+            },
+            Data::Collection(data) => data
+                .into_iter()
+                .enumerate()
+                .map(|(index, item)| self.at(format!("{}/{}", pointer, index), |ctx| f(item, ctx)))
+                .collect(),
+        }
+    }
+
+    /// Returns the included resource that matches `ident`.
+    pub(crate) fn get(&self, ident: &Identifier) -> Option<&'a Object> {
+        self.incl.into_iter().find(|item| *ident == **item)
+    }
+
+    /// Flattens the included resource that matches `ident`.
+    ///
+    /// Returns `None` if there is no matching resource or if the matching resource is
+    /// already being flattened.
+    pub(crate) fn resolve(&mut self, ident: &Identifier) -> Result<Option<Value>, Error> {
+        if self.entered(ident) {
+            return Ok(None);
+        }
+
+        let found = self
+            .incl
+            .into_iter()
+            .enumerate()
+            .find(|(_, item)| *ident == **item);
+
+        match found {
+            Some((index, item)) => self
+                .at(format!("/included/{}", index), |ctx| {
+                    item.clone().flatten(ctx)
+                })
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns `true` if the resource that `ident` references is being flattened. An
+    /// identifier without an `id` matches a resource with the same `lid`.
+    fn entered(&self, ident: &Identifier) -> bool {
+        self.stack.iter().any(|item| {
+            let matches = match ident.id {
+                Some(ref id) => item.id.as_ref() == Some(id),
+                None => ident.lid.is_some() && item.lid == ident.lid,
+            };
+
+            matches && item.kind == ident.kind
+        })
+    }
+
+    /// Returns `true` if an included resource matches `ident`.
+    pub(crate) fn includes(&self, ident: &Identifier) -> bool {
+        self.get(ident).is_some()
+    }
+
+    /// Marks the resource identified by `ident` as being flattened. Returns an error if
+    /// this would exceed the maximum depth.
+    pub(crate) fn enter(&mut self, ident: Identifier) -> Result<(), Error> {
+        if self.stack.len() > self.max_depth {
+            return Err(Error::depth_limit_exceeded(self.max_depth));
+        }
+
+        self.stack.push(ident);
+        Ok(())
+    }
+
+    /// Marks the resource that was last entered as flattened.
+    pub(crate) fn exit(&mut self) {
+        self.stack.pop();
+    }
+}
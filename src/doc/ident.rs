@@ -4,36 +4,45 @@ use std::{
     mem,
 };
 
-use serde::{Deserialize, Serialize};
+use serde::{de::Deserializer, Deserialize, Serialize};
 
 use crate::{
-    doc::{Data, Document, Object, PrimaryData},
+    doc::{validate::Members, Data, Document, Flattener, Object, PrimaryData},
     error::Error,
     query::Query,
     sealed::Sealed,
-    value::{Key, Map, Set, Value},
+    value::{Key, Map, Value},
     view::Render,
 };
 
 /// Identifies an individual resource. Commonly found in an object's relationships.
 ///
-/// Identifiers share their [equality] and [hashing] behavior with [`Object`]. For more
-/// information, check out the *[resource identifier objects]* section of the
-/// JSON API specification.
+/// An identifier contains an [`id`], a [`lid`] or both. The [`lid`] member is used to
+/// reference a resource that does not have an [`id`] yet, because it is created by the
+/// same request. Identifiers share their [equality] and [hashing] behavior with
+/// [`Object`], except that an identifier without an [`id`] is identified by its
+/// [`lid`] instead. An identifier that contains neither an [`id`] nor a [`lid`] can not
+/// be deserialized. For more information, check out the *[resource identifier
+/// objects]* section of the JSON API specification.
 ///
 /// [`Object`]: ./struct.Object.html
+/// [`id`]: #structfield.id
+/// [`lid`]: #structfield.lid
 /// [equality]: ./struct.Object.html#equality
 /// [hashing]: ./struct.Object.html#hashing
 /// [resource identifier objects]: https://goo.gl/vgfzru
 #[non_exhaustive]
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Identifier {
-    /// A string that contains a unique identfier for this resource type (`kind`). For
-    /// more information, check out the *[identification]* section of the JSON API
-    /// specification.
+    /// An optional string that contains a unique identfier for this resource type
+    /// (`kind`). This value is only `None` if the identifier references a resource by
+    /// its [`lid`]. For more information, check out the *[identification]* section of
+    /// the JSON API specification.
     ///
+    /// [`lid`]: #structfield.lid
     /// [identification]: https://goo.gl/3s681i
-    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
 
     /// Describes resources that share common attributes and relationships. This field is
     /// derived from the `type` field if the identifier is deserialized. For more
@@ -44,6 +53,23 @@ pub struct Identifier {
     #[serde(rename = "type")]
     pub kind: Key,
 
+    /// An optional string that uniquely identifies a resource that is created by the
+    /// same request. For more information, check out the *[identification]* section of
+    /// the JSON API specification.
+    ///
+    /// When a document is interpreted with [`from_doc`], a `lid` is resolved against the
+    /// included resources of the document. Included resources are [`Object`]s, which
+    /// always have an `id`, so a `lid` can only reference an included resource that
+    /// also has an `id`. The linkage of a [`NewObject`] is interpreted as that `id`,
+    /// just like linkage that has an `id` of its own.
+    ///
+    /// [`from_doc`]: ../fn.from_doc.html
+    /// [`NewObject`]: ./struct.NewObject.html
+    /// [`Object`]: ./struct.Object.html
+    /// [identification]: https://jsonapi.org/format/1.1/#document-resource-object-identification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lid: Option<String>,
+
     /// Non-standard meta information. If this value of this field is empty, it will not
     /// be serialized. For more information, check out the *[meta information]* section
     /// of the JSON API specification.
@@ -75,20 +101,135 @@ impl Identifier {
     /// ```
     pub fn new(kind: Key, id: String) -> Self {
         Identifier {
-            id,
             kind,
+            id: Some(id),
+            lid: None,
             meta: Default::default(),
         }
     }
+
+    /// Returns a new `Identifier` that references a resource by its local id.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Identifier, NewObject};
+    ///
+    /// let mut user = NewObject::new("users".parse()?);
+    /// user.lid = Some("a".to_owned());
+    ///
+    /// let ident = Identifier::with_lid("users".parse()?, "a".to_owned());
+    /// assert_eq!(ident.id, None);
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn with_lid(kind: Key, lid: String) -> Self {
+        Identifier {
+            kind,
+            id: None,
+            lid: Some(lid),
+            meta: Default::default(),
+        }
+    }
+
+    /// Returns the id of `self` as a value. If the identifier references a resource by
+    /// its `lid`, the id of the matching included object is returned instead, so both
+    /// kinds of linkage have the same shape.
+    pub(crate) fn flatten_local(self, ctx: &mut Flattener) -> Result<Value, Error> {
+        if let Some(id) = self.id {
+            return Ok(id.into());
+        }
+
+        match ctx.get(&self) {
+            Some(object) => Ok(object.id.clone().into()),
+            None => Err(self.unresolved(ctx)),
+        }
+    }
+
+    /// Returns an error that references the linkage at the pointer of `ctx`, because no
+    /// included resource matches `self`.
+    fn unresolved(&self, ctx: &Flattener) -> Error {
+        match self.lid {
+            Some(ref lid) => Error::invalid_member(
+                ctx.pointer(),
+                &format!(r#"no included resource has the local id "{}""#, lid),
+            ),
+            None => Error::missing_member(&format!("{}/id", ctx.pointer())),
+        }
+    }
+
+    /// Returns the member that identifies `self`. If the identifier has an `id`, the
+    /// `lid` is not taken into account.
+    fn key(&self) -> (Option<&str>, Option<&str>) {
+        match self.id {
+            Some(ref id) => (Some(id), None),
+            None => (None, self.lid.as_deref()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Identifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error as DeError;
+
+        #[derive(Deserialize)]
+        struct RawIdentifier {
+            #[serde(default)]
+            id: Option<String>,
+            #[serde(rename = "type")]
+            kind: Key,
+            #[serde(default)]
+            lid: Option<String>,
+            #[serde(default)]
+            meta: Map,
+        }
+
+        let RawIdentifier {
+            id,
+            kind,
+            lid,
+            meta,
+        } = RawIdentifier::deserialize(deserializer)?;
+
+        if id.is_none() && lid.is_none() {
+            return Err(D::Error::custom(Error::missing_member("/id")));
+        }
+
+        Ok(Identifier {
+            id,
+            kind,
+            lid,
+            meta,
+        })
+    }
 }
 
 impl Eq for Identifier {}
 
 impl From<Object> for Identifier {
     fn from(object: Object) -> Self {
-        let Object { id, kind, meta, .. } = object;
+        let Object {
+            id,
+            kind,
+            lid,
+            meta,
+            ..
+        } = object;
         let mut ident = Identifier::new(kind, id);
 
+        ident.lid = lid;
         ident.meta = meta;
         ident
     }
@@ -102,20 +243,26 @@ impl<'a> From<&'a Object> for Identifier {
 
 impl Hash for Identifier {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.id.hash(state);
+        self.key().hash(state);
         self.kind.hash(state);
     }
 }
 
 impl PartialEq for Identifier {
     fn eq(&self, rhs: &Identifier) -> bool {
-        self.id == rhs.id && self.kind == rhs.kind
+        self.key() == rhs.key() && self.kind == rhs.kind
     }
 }
 
 impl PartialEq<Object> for Identifier {
     fn eq(&self, rhs: &Object) -> bool {
-        self.id == rhs.id && self.kind == rhs.kind
+        let matches = match self.key() {
+            (Some(id), _) => rhs.id == id,
+            (None, Some(lid)) => rhs.lid.as_deref() == Some(lid),
+            (None, None) => false,
+        };
+
+        matches && self.kind == rhs.kind
     }
 }
 
@@ -146,11 +293,31 @@ impl Render<Identifier> for Vec<Identifier> {
 }
 
 impl PrimaryData for Identifier {
-    fn flatten(self, incl: &Set<Object>) -> Value {
-        incl.into_iter()
-            .find(|item| self == **item)
-            .map(|item| item.clone().flatten(incl))
-            .unwrap_or_else(|| self.id.clone().into())
+    fn members(&self) -> Members<'_> {
+        Members {
+            kind: &self.kind,
+            id: self.id.as_deref(),
+            lid: self.lid.as_deref(),
+            attributes: None,
+            relationships: None,
+        }
+    }
+
+    fn flatten(self, ctx: &mut Flattener) -> Result<Value, Error> {
+        if let Some(value) = ctx.resolve(&self)? {
+            return Ok(value);
+        }
+
+        match self.id {
+            Some(id) => Ok(id.into()),
+            // A matching resource that was not resolved is being flattened, so the
+            // reference is cyclic. Without an id, it can not be replaced.
+            None if ctx.includes(&self) => Err(Error::invalid_member(
+                ctx.pointer(),
+                "the related resource is an ancestor of itself",
+            )),
+            None => Err(self.unresolved(ctx)),
+        }
     }
 }
 
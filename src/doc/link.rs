@@ -45,15 +45,46 @@ pub struct Link {
     /// The link’s URI.
     pub href: Uri,
 
-    /// Non-standard meta information. If this value of this field is empty, the link
-    /// will be serialized as a string containing the contents of `href`. For more
-    /// information, check out the *[meta information]* section of the JSON API
-    /// specification.
+    /// The [link relation type] of the link. Introduced in JSON API 1.1.
+    ///
+    /// [link relation type]: https://tools.ietf.org/html/rfc8288#section-2.1
+    pub rel: Option<String>,
+
+    /// A link to a description document, such as a schema, for the target of the link.
+    /// Introduced in JSON API 1.1.
+    pub describedby: Option<Box<Link>>,
+
+    /// A human-readable label for the target of the link. Introduced in JSON API 1.1.
+    pub title: Option<String>,
+
+    /// The media type of the target of the link. Introduced in JSON API 1.1.
+    pub kind: Option<String>,
+
+    /// The language(s) of the target of the link. Introduced in JSON API 1.1.
+    pub hreflang: Vec<String>,
+
+    /// Non-standard meta information. If this field and the other members of the link
+    /// object are empty, the link will be serialized as a string containing the
+    /// contents of `href`. For more information, check out the *[meta information]*
+    /// section of the JSON API specification.
     ///
     /// [meta information]: https://goo.gl/LyrGF8
     pub meta: Map,
 }
 
+impl Link {
+    /// Returns `true` if the link has no members other than `href`, so it can be
+    /// serialized as a string.
+    fn is_bare(&self) -> bool {
+        self.meta.is_empty()
+            && self.rel.is_none()
+            && self.describedby.is_none()
+            && self.title.is_none()
+            && self.kind.is_none()
+            && self.hreflang.is_empty()
+    }
+}
+
 impl Deref for Link {
     type Target = Uri;
 
@@ -76,7 +107,7 @@ impl FromStr for Link {
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         Ok(Link {
             href: value.parse()?,
-            meta: Default::default(),
+            ..Default::default()
         })
     }
 }
@@ -121,6 +152,21 @@ impl<'de> Deserialize<'de> for Link {
         enum Field {
             Href,
             Meta,
+            Rel,
+            Describedby,
+            Title,
+            #[serde(rename = "type")]
+            Kind,
+            Hreflang,
+            #[serde(other)]
+            Other,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Hreflang {
+            One(String),
+            Many(Vec<String>),
         }
 
         struct LinkVisitor;
@@ -145,6 +191,11 @@ impl<'de> Deserialize<'de> for Link {
             {
                 let mut href = None;
                 let mut meta = None;
+                let mut rel = None;
+                let mut describedby = None;
+                let mut title = None;
+                let mut kind = None;
+                let mut hreflang = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -154,6 +205,21 @@ impl<'de> Deserialize<'de> for Link {
                         Field::Meta if meta.is_some() => {
                             return Err(de::Error::duplicate_field("meta"))
                         }
+                        Field::Rel if rel.is_some() => {
+                            return Err(de::Error::duplicate_field("rel"))
+                        }
+                        Field::Describedby if describedby.is_some() => {
+                            return Err(de::Error::duplicate_field("describedby"))
+                        }
+                        Field::Title if title.is_some() => {
+                            return Err(de::Error::duplicate_field("title"))
+                        }
+                        Field::Kind if kind.is_some() => {
+                            return Err(de::Error::duplicate_field("type"))
+                        }
+                        Field::Hreflang if hreflang.is_some() => {
+                            return Err(de::Error::duplicate_field("hreflang"))
+                        }
                         Field::Href => {
                             let next = map.next_value::<String>()?;
                             href = Some(next.parse().map_err(de::Error::custom)?);
@@ -161,12 +227,38 @@ impl<'de> Deserialize<'de> for Link {
                         Field::Meta => {
                             meta = Some(map.next_value()?);
                         }
+                        Field::Rel => {
+                            rel = Some(map.next_value()?);
+                        }
+                        Field::Describedby => {
+                            describedby = Some(Box::new(map.next_value()?));
+                        }
+                        Field::Title => {
+                            title = Some(map.next_value()?);
+                        }
+                        Field::Kind => {
+                            kind = Some(map.next_value()?);
+                        }
+                        Field::Hreflang => {
+                            hreflang = Some(match map.next_value()? {
+                                Hreflang::One(value) => vec![value],
+                                Hreflang::Many(values) => values,
+                            });
+                        }
+                        Field::Other => {
+                            map.next_value::<de::IgnoredAny>()?;
+                        }
                     }
                 }
 
                 Ok(Link {
                     href: href.ok_or_else(|| de::Error::missing_field("href"))?,
                     meta: meta.unwrap_or_default(),
+                    rel,
+                    describedby,
+                    title,
+                    kind,
+                    hreflang: hreflang.unwrap_or_default(),
                 })
             }
         }
@@ -181,17 +273,41 @@ impl Serialize for Link {
         S: Serializer,
     {
         let href = self.href.to_string();
-        let meta = &self.meta;
 
-        if meta.is_empty() {
-            serializer.serialize_str(&href)
-        } else {
-            let mut state = serializer.serialize_struct("Link", 2)?;
+        if self.is_bare() {
+            return serializer.serialize_str(&href);
+        }
+
+        let mut state = serializer.serialize_struct("Link", 7)?;
+
+        state.serialize_field("href", &href)?;
+
+        if let Some(ref rel) = self.rel {
+            state.serialize_field("rel", rel)?;
+        }
 
-            state.serialize_field("href", &href)?;
-            state.serialize_field("meta", meta)?;
+        if let Some(ref describedby) = self.describedby {
+            state.serialize_field("describedby", describedby)?;
+        }
 
-            state.end()
+        if let Some(ref title) = self.title {
+            state.serialize_field("title", title)?;
         }
+
+        if let Some(ref kind) = self.kind {
+            state.serialize_field("type", kind)?;
+        }
+
+        match self.hreflang.len() {
+            0 => {}
+            1 => state.serialize_field("hreflang", &self.hreflang[0])?,
+            _ => state.serialize_field("hreflang", &self.hreflang)?,
+        }
+
+        if !self.meta.is_empty() {
+            state.serialize_field("meta", &self.meta)?;
+        }
+
+        state.end()
     }
 }
@@ -1,14 +1,19 @@
 //! Components of a JSON API document.
 
 mod convert;
+mod flatten;
 mod ident;
 mod link;
 mod object;
 mod relationship;
+mod resolve;
 mod specification;
 
 mod error;
 
+pub mod atomic;
+pub mod validate;
+
 use std::iter::FromIterator;
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -24,18 +29,29 @@ use crate::{
 pub use self::{
     convert::*,
     error::{ErrorObject, ErrorSource},
+    flatten::DEFAULT_MAX_DEPTH,
     ident::Identifier,
     link::Link,
     object::{NewObject, Object},
     relationship::Relationship,
+    resolve::{resolve_many, resolve_one, FromObject, Resolver},
     specification::{JsonApi, Version},
 };
 
+#[doc(hidden)]
+pub use self::flatten::Flattener;
+
+#[cfg(feature = "derive")]
+pub use json_api_derive::FromObject;
+
 /// A marker trait used to indicate that a type can be the primary data for a
 /// document.
 pub trait PrimaryData: DeserializeOwned + Sealed + Serialize {
     #[doc(hidden)]
-    fn flatten(self, set: &Set<Object>) -> Value;
+    fn flatten(self, ctx: &mut Flattener) -> Result<Value, Error>;
+
+    #[doc(hidden)]
+    fn members(&self) -> validate::Members<'_>;
 }
 
 /// Represents a compound JSON API document.
@@ -98,13 +114,27 @@ pub enum Document<T: PrimaryData> {
         #[serde(default, skip_serializing_if = "Map::is_empty")]
         meta: Map,
     },
+    /// Contains only top-level meta information, for example in response to a
+    /// successful `DELETE` request. For more information, check out the *[deleting
+    /// resources]* section of the JSON API specification.
+    ///
+    /// [deleting resources]: https://jsonapi.org/format/#crud-deleting-responses-200
+    Meta {
+        #[serde(default)]
+        jsonapi: JsonApi,
+
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        links: Map<Key, Link>,
+
+        meta: Map,
+    },
 }
 
 impl<T: PrimaryData> Document<T> {
     /// Returns `true` if the document does not contain any errors.
     pub fn is_ok(&self) -> bool {
         match *self {
-            Document::Ok { .. } => true,
+            Document::Ok { .. } | Document::Meta { .. } => true,
             Document::Err { .. } => false,
         }
     }
@@ -113,9 +143,94 @@ impl<T: PrimaryData> Document<T> {
     pub fn is_err(&self) -> bool {
         match *self {
             Document::Ok { .. } => true,
-            Document::Err { .. } => false,
+            Document::Err { .. } | Document::Meta { .. } => false,
         }
     }
+
+    /// Returns a reference to the [JSON API object] of the document.
+    ///
+    /// [JSON API object]: https://goo.gl/hZUcEt
+    pub fn jsonapi(&self) -> &JsonApi {
+        match *self {
+            Document::Ok { ref jsonapi, .. }
+            | Document::Err { ref jsonapi, .. }
+            | Document::Meta { ref jsonapi, .. } => jsonapi,
+        }
+    }
+
+    /// Returns a mutable reference to the [JSON API object] of the document.
+    ///
+    /// [JSON API object]: https://goo.gl/hZUcEt
+    pub fn jsonapi_mut(&mut self) -> &mut JsonApi {
+        match *self {
+            Document::Ok {
+                ref mut jsonapi, ..
+            }
+            | Document::Err {
+                ref mut jsonapi, ..
+            }
+            | Document::Meta {
+                ref mut jsonapi, ..
+            } => jsonapi,
+        }
+    }
+
+    /// Returns a reference to the top-level links of the document.
+    pub fn links(&self) -> &Map<Key, Link> {
+        match *self {
+            Document::Ok { ref links, .. }
+            | Document::Err { ref links, .. }
+            | Document::Meta { ref links, .. } => links,
+        }
+    }
+
+    /// Returns a mutable reference to the top-level links of the document.
+    pub fn links_mut(&mut self) -> &mut Map<Key, Link> {
+        match *self {
+            Document::Ok { ref mut links, .. }
+            | Document::Err { ref mut links, .. }
+            | Document::Meta { ref mut links, .. } => links,
+        }
+    }
+
+    /// Sets the version advertised by the document to `version`.
+    ///
+    /// If the document's [JSON API object] uses members that were introduced in a
+    /// later version of the specification than `version`, the lowest version that can
+    /// represent them is advertised instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{Document, Object, Version};
+    ///
+    /// let obj = Object::new("users".parse()?, "1".to_owned());
+    /// let doc: Document<Object> = json_api::to_doc(obj, None)?.with_version(Version::V1_1);
+    ///
+    /// assert_eq!(doc.jsonapi().version, Version::V1_1);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [JSON API object]: https://goo.gl/hZUcEt
+    pub fn with_version(mut self, version: Version) -> Self {
+        let jsonapi = self.jsonapi_mut();
+
+        jsonapi.version = version;
+        jsonapi.version = jsonapi.required_version();
+
+        self
+    }
 }
 
 impl<T: PrimaryData> Render<T> for Document<T> {
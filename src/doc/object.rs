@@ -7,11 +7,13 @@ use std::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    doc::{Data, Document, Identifier, Link, PrimaryData, Relationship},
+    doc::{
+        validate::Members, Data, Document, Flattener, Identifier, Link, PrimaryData, Relationship,
+    },
     error::Error,
     query::Query,
     sealed::Sealed,
-    value::{Key, Map, Set, Value},
+    value::{Key, Map, Value},
     view::Render,
 };
 
@@ -173,6 +175,15 @@ pub struct Object {
     #[serde(rename = "type")]
     pub kind: Key,
 
+    /// An optional string that uniquely identifies this resource within the request
+    /// that created it. Servers may echo the `lid` of a newly created resource so the
+    /// client can correlate it with its local representation. For more information,
+    /// check out the *[identification]* section of the JSON API specification.
+    ///
+    /// [identification]: https://jsonapi.org/format/1.1/#document-resource-object-identification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lid: Option<String>,
+
     /// Contains relevant links. If this value of this field is empty, it will not be
     /// serialized. For more information, check out the *[links]* section of the JSON
     /// API specification.
@@ -223,6 +234,7 @@ impl Object {
             id,
             kind,
             attributes: Default::default(),
+            lid: Default::default(),
             links: Default::default(),
             meta: Default::default(),
             relationships: Default::default(),
@@ -247,7 +259,7 @@ impl PartialEq for Object {
 
 impl PartialEq<Identifier> for Object {
     fn eq(&self, rhs: &Identifier) -> bool {
-        self.id == rhs.id && self.kind == rhs.kind
+        *rhs == *self
     }
 }
 
@@ -299,7 +311,24 @@ impl Render<Object> for Vec<Object> {
 }
 
 impl PrimaryData for Object {
-    fn flatten(self, incl: &Set<Object>) -> Value {
+    fn members(&self) -> Members<'_> {
+        Members {
+            kind: &self.kind,
+            id: Some(&self.id),
+            lid: self.lid.as_deref(),
+            attributes: Some(&self.attributes),
+            relationships: Some(&self.relationships),
+        }
+    }
+
+    fn flatten(self, ctx: &mut Flattener) -> Result<Value, Error> {
+        ctx.enter(Identifier {
+            id: Some(self.id.clone()),
+            kind: self.kind.clone(),
+            lid: self.lid.clone(),
+            meta: Default::default(),
+        })?;
+
         #[rustfmt::skip]
         let Object { id, attributes, relationships, .. } = self;
         let mut map = {
@@ -311,21 +340,14 @@ impl PrimaryData for Object {
         map.extend(attributes);
 
         for (key, value) in relationships {
-            let value = match value.data {
-                Data::Member(data) => match *data {
-                    Some(item) => item.flatten(incl),
-                    None => Value::Null,
-                },
-                Data::Collection(data) => {
-                    let iter = data.into_iter().map(|item| item.flatten(incl));
-                    Value::Array(iter.collect())
-                }
-            };
-
-            map.insert(key, value);
+            if let Some(data) = value.data {
+                let value = ctx.linkage(&key, data, Identifier::flatten)?;
+                map.insert(key, value);
+            }
         }
 
-        Value::Object(map)
+        ctx.exit();
+        Ok(Value::Object(map))
     }
 }
 
@@ -367,6 +389,16 @@ pub struct NewObject {
     #[serde(rename = "type")]
     pub kind: Key,
 
+    /// An optional string that uniquely identifies this resource within the request
+    /// that creates it. Other resources in the same request can reference this
+    /// resource by using the same value as the `lid` of a resource identifier. For
+    /// more information, check out the *[identification]* section of the JSON API
+    /// specification.
+    ///
+    /// [identification]: https://jsonapi.org/format/1.1/#document-resource-object-identification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lid: Option<String>,
+
     /// Contains relevant links. If this value of this field is empty, it will not be
     /// serialized. For more information, check out the *[links]* section of the JSON
     /// API specification.
@@ -417,6 +449,7 @@ impl NewObject {
             kind,
             id: Default::default(),
             attributes: Default::default(),
+            lid: Default::default(),
             links: Default::default(),
             meta: Default::default(),
             relationships: Default::default(),
@@ -425,7 +458,24 @@ impl NewObject {
 }
 
 impl PrimaryData for NewObject {
-    fn flatten(self, _: &Set<Object>) -> Value {
+    fn members(&self) -> Members<'_> {
+        Members {
+            kind: &self.kind,
+            id: self.id.as_deref(),
+            lid: self.lid.as_deref(),
+            attributes: Some(&self.attributes),
+            relationships: Some(&self.relationships),
+        }
+    }
+
+    fn flatten(self, ctx: &mut Flattener) -> Result<Value, Error> {
+        ctx.enter(Identifier {
+            id: self.id.clone(),
+            kind: self.kind.clone(),
+            lid: self.lid.clone(),
+            meta: Default::default(),
+        })?;
+
         #[rustfmt::skip]
         let NewObject { id, attributes, relationships, .. } = self;
         let mut map = {
@@ -440,18 +490,14 @@ impl PrimaryData for NewObject {
         map.extend(attributes);
 
         for (key, value) in relationships {
-            let value = match value.data {
-                Data::Member(data) => match *data {
-                    Some(Identifier { id, .. }) => Value::String(id),
-                    None => Value::Null,
-                },
-                Data::Collection(data) => data.into_iter().map(|ident| ident.id).collect(),
-            };
-
-            map.insert(key, value);
+            if let Some(data) = value.data {
+                let value = ctx.linkage(&key, data, Identifier::flatten_local)?;
+                map.insert(key, value);
+            }
         }
 
-        Value::Object(map)
+        ctx.exit();
+        Ok(Value::Object(map))
     }
 }
 
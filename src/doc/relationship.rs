@@ -1,6 +1,6 @@
 use std::iter::FromIterator;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{
     doc::{Data, Identifier, Link},
@@ -9,18 +9,30 @@ use crate::{
 
 /// Represents a resource's relationship to another.
 ///
+/// A relationship contains at least one of resource linkage, links or meta
+/// information. Relationships without resource linkage are commonly used for large
+/// to-many relationships that are never embedded in a document.
+///
 /// For more information, check out the *[relationships]* section of the JSON API
 /// specification.
 ///
 /// [relationships]: https://goo.gl/ZQw9Xr
 #[non_exhaustive]
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct Relationship {
-    /// Contains resource linkage. For more information, checkout the
-    /// *[resource linkage]* section of the JSON API specification.
+    /// Contains resource linkage. A `None` value indicates that the `data` member is
+    /// absent, while a `null` value of the `data` member is represented by an empty
+    /// `Data::Member`. If this value is `None`, it will not be serialized. For more
+    /// information, checkout the *[resource linkage]* section of the JSON API
+    /// specification.
     ///
     /// [resource linkage]: https://goo.gl/evZF8m
-    pub data: Data<Identifier>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_data",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub data: Option<Data<Identifier>>,
 
     /// Contains relevant links. If this value of this field is empty, it will not be
     /// serialized. For more information, check out the *[links]* section of the JSON
@@ -64,11 +76,45 @@ impl Relationship {
     /// ```
     pub fn new(data: Data<Identifier>) -> Self {
         Relationship {
-            data,
+            data: Some(data),
             links: Default::default(),
             meta: Default::default(),
         }
     }
+
+    /// Returns a new `Relationship` with the specified `links` and without resource
+    /// linkage.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::Relationship;
+    /// use json_api::value::Map;
+    ///
+    /// let mut links = Map::new();
+    /// links.insert("related".parse()?, "/users/1/posts".parse()?);
+    ///
+    /// let relationship = Relationship::from_links(links);
+    /// assert!(relationship.data.is_none());
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn from_links(links: Map<Key, Link>) -> Self {
+        Relationship {
+            links,
+            data: None,
+            meta: Default::default(),
+        }
+    }
 }
 
 impl From<Option<Identifier>> for Relationship {
@@ -100,3 +146,12 @@ impl FromIterator<Identifier> for Relationship {
         Relationship::new(data)
     }
 }
+
+/// Deserialize the `data` member of a relationship. A `null` value is interpreted as
+/// an empty `Data::Member` so it can be distinguished from a missing member.
+fn deserialize_data<'de, D>(deserializer: D) -> Result<Option<Data<Identifier>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Data::deserialize(deserializer).map(Some)
+}
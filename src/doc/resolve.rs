@@ -0,0 +1,365 @@
+use std::{cell::RefCell, collections::HashMap, iter::FromIterator, str::FromStr};
+
+use serde::de::DeserializeOwned;
+
+use crate::{
+    doc::{Data, Document, Identifier, Object},
+    error::Error,
+    value::{self, Key, Set, Value},
+};
+
+/// A type that can be built from a resource [`Object`].
+///
+/// Unlike [`from_doc`], which flattens a document into a single JSON value before
+/// deserializing it, implementations of this trait read attributes and relationships
+/// separately. Related resources are resolved through a [`Resolver`] and errors point
+/// to the member of the document that caused them.
+///
+/// This trait can be implemented with `#[derive(FromObject)]` when the `derive` feature
+/// is enabled.
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::{self, FromObject, Object, Resolver};
+///
+/// struct Post {
+///     id: u64,
+///     title: String,
+///     author: Option<User>,
+/// }
+///
+/// struct User {
+///     id: u64,
+///     name: String,
+/// }
+///
+/// impl FromObject for Post {
+///     fn from_object(object: &Object, resolver: &Resolver) -> Result<Self, Error> {
+///         Ok(Post {
+///             id: resolver.id(object)?,
+///             title: resolver.attribute(object, "title")?,
+///             author: resolver.has_one(object, "author")?,
+///         })
+///     }
+/// }
+///
+/// impl FromObject for User {
+///     fn from_object(object: &Object, resolver: &Resolver) -> Result<Self, Error> {
+///         Ok(User {
+///             id: resolver.id(object)?,
+///             name: resolver.attribute(object, "name")?,
+///         })
+///     }
+/// }
+///
+/// let doc = serde_json::from_str(r#"{
+///     "data": {
+///         "type": "posts",
+///         "id": "1",
+///         "attributes": { "title": "Hello, World!" },
+///         "relationships": {
+///             "author": { "data": { "type": "users", "id": "2" } }
+///         }
+///     },
+///     "included": [{
+///         "type": "users",
+///         "id": "2",
+///         "attributes": { "name": "Jane Doe" }
+///     }]
+/// }"#)?;
+///
+/// let post = doc::resolve_one::<Post>(&doc)?.unwrap();
+/// let author = post.author.unwrap();
+///
+/// assert_eq!(post.id, 1);
+/// assert_eq!(post.title, "Hello, World!");
+/// assert_eq!(author.name, "Jane Doe");
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`from_doc`]: ../fn.from_doc.html
+pub trait FromObject: Sized {
+    /// Builds `Self` from `object`, resolving related resources with `resolver`.
+    fn from_object(object: &Object, resolver: &Resolver) -> Result<Self, Error>;
+}
+
+/// An id-indexed view of the resource objects in a compound document.
+///
+/// A `Resolver` is passed to [`FromObject::from_object`] and provides methods to read
+/// the members of a resource object. Errors returned from these methods contain the
+/// [JSON pointer] of the member that caused them.
+///
+/// [`FromObject::from_object`]: trait.FromObject.html#tymethod.from_object
+/// [JSON pointer]: https://tools.ietf.org/html/rfc6901
+#[derive(Debug, Default)]
+pub struct Resolver<'a> {
+    index: HashMap<&'a str, HashMap<&'a str, (String, &'a Object)>>,
+    stack: RefCell<Vec<(&'a str, &'a str)>>,
+}
+
+impl<'a> Resolver<'a> {
+    /// Returns a new `Resolver` for the primary `data` and `included` resources of a
+    /// document.
+    pub fn new(data: &'a Data<Object>, included: &'a Set<Object>) -> Self {
+        let mut resolver = Resolver::default();
+
+        for (index, object) in included.iter().enumerate() {
+            resolver.insert(format!("/included/{}", index), object);
+        }
+
+        match *data {
+            Data::Member(ref data) => {
+                if let Some(ref object) = **data {
+                    resolver.insert("/data".to_owned(), object);
+                }
+            }
+            Data::Collection(ref data) => {
+                for (index, object) in data.iter().enumerate() {
+                    resolver.insert(format!("/data/{}", index), object);
+                }
+            }
+        }
+
+        resolver
+    }
+
+    /// Returns the resource object identified by `ident`, if it is present in the
+    /// document.
+    pub fn get(&self, ident: &Identifier) -> Option<&'a Object> {
+        let id = ident.id.as_ref()?;
+
+        self.lookup(&ident.kind, id).map(|&(_, object)| object)
+    }
+
+    /// Returns the JSON pointer to `object` within the document. If `object` is not
+    /// part of the document, an empty string is returned.
+    pub fn pointer(&self, object: &Object) -> &str {
+        self.lookup(&object.kind, &object.id)
+            .map_or("", |(pointer, _)| pointer)
+    }
+
+    /// Parses the `id` of `object` as a type `T`.
+    pub fn id<T>(&self, object: &Object) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: ToString,
+    {
+        object.id.parse().map_err(|e: T::Err| {
+            let pointer = format!("{}/id", self.pointer(object));
+            Error::invalid_member(&pointer, &e.to_string())
+        })
+    }
+
+    /// Returns an error if `object` is not of the type `kind`.
+    pub fn expect_kind(&self, object: &Object, kind: &str) -> Result<(), Error> {
+        if object.kind == *kind {
+            return Ok(());
+        }
+
+        let pointer = format!("{}/type", self.pointer(object));
+        let reason = format!(r#"expected type "{}", found "{}""#, kind, object.kind);
+
+        Err(Error::invalid_member(&pointer, &reason))
+    }
+
+    /// Deserializes the attribute `name` of `object` as a type `T`.
+    ///
+    /// The `name` is parsed as a [`Key`], so a field such as `published_at` reads the
+    /// attribute `published-at`. A missing attribute is treated as `null`. This allows a
+    /// missing attribute to be read as an `Option<T>`.
+    ///
+    /// [`Key`]: ../value/struct.Key.html
+    pub fn attribute<T>(&self, object: &Object, name: &str) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let name = name.parse::<Key>()?;
+        let pointer = format!("{}/attributes/{}", self.pointer(object), name);
+
+        match object.attributes.get(&name) {
+            Some(value) => value::from_value(value.clone())
+                .map_err(|e| Error::invalid_member(&pointer, &e.to_string())),
+            None => value::from_value(Value::Null).map_err(|_| Error::missing_member(&pointer)),
+        }
+    }
+
+    /// Resolves the related resource of the to-one relationship `name` of `object`. The
+    /// `name` is parsed as a [`Key`].
+    ///
+    /// Returns `None` if the relationship is missing, does not contain resource linkage,
+    /// or is empty.
+    ///
+    /// [`Key`]: ../value/struct.Key.html
+    pub fn has_one<T>(&self, object: &Object, name: &str) -> Result<Option<T>, Error>
+    where
+        T: FromObject,
+    {
+        let name = name.parse::<Key>()?;
+        let pointer = format!("{}/relationships/{}/data", self.pointer(object), name);
+
+        match object
+            .relationships
+            .get(&name)
+            .and_then(|rel| rel.data.as_ref())
+        {
+            Some(Data::Member(data)) => match **data {
+                Some(ref ident) => self.resolve(ident, &pointer).map(Some),
+                None => Ok(None),
+            },
+            Some(Data::Collection(_)) => Err(Error::invalid_member(
+                &pointer,
+                "expected a to-one relationship",
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Resolves the related resources of the to-many relationship `name` of `object`.
+    /// The `name` is parsed as a [`Key`].
+    ///
+    /// Returns an empty collection if the relationship is missing or does not contain
+    /// resource linkage.
+    ///
+    /// [`Key`]: ../value/struct.Key.html
+    pub fn has_many<T, C>(&self, object: &Object, name: &str) -> Result<C, Error>
+    where
+        T: FromObject,
+        C: FromIterator<T>,
+    {
+        let name = name.parse::<Key>()?;
+        let pointer = format!("{}/relationships/{}/data", self.pointer(object), name);
+
+        match object
+            .relationships
+            .get(&name)
+            .and_then(|rel| rel.data.as_ref())
+        {
+            Some(Data::Collection(data)) => data
+                .iter()
+                .enumerate()
+                .map(|(index, ident)| self.resolve(ident, &format!("{}/{}", pointer, index)))
+                .collect(),
+            Some(Data::Member(_)) => Err(Error::invalid_member(
+                &pointer,
+                "expected a to-many relationship",
+            )),
+            None => Ok(C::from_iter(None)),
+        }
+    }
+
+    fn insert(&mut self, pointer: String, object: &'a Object) {
+        self.index
+            .entry(&object.kind)
+            .or_default()
+            .entry(&object.id)
+            .or_insert((pointer, object));
+    }
+
+    fn lookup(&self, kind: &str, id: &str) -> Option<&(String, &'a Object)> {
+        self.index.get(kind)?.get(id)
+    }
+
+    fn resolve<T: FromObject>(&self, ident: &Identifier, pointer: &str) -> Result<T, Error> {
+        let object = match self.get(ident) {
+            Some(object) => object,
+            None => {
+                let reason = "the related resource is not present in the document";
+                return Err(Error::invalid_member(pointer, reason));
+            }
+        };
+
+        let key = (&*object.kind, &*object.id);
+
+        if self.stack.borrow().contains(&key) {
+            let reason = "the related resource is an ancestor of itself";
+            return Err(Error::invalid_member(pointer, reason));
+        }
+
+        self.stack.borrow_mut().push(key);
+        let result = T::from_object(object, self);
+        self.stack.borrow_mut().pop();
+
+        result
+    }
+}
+
+/// Interpret the primary data of a `Document<Object>` as a single type `U`.
+///
+/// Returns `None` if the primary data is `null`. Returns an error if the primary data is
+/// a collection or the document contains errors.
+pub fn resolve_one<U>(doc: &Document<Object>) -> Result<Option<U>, Error>
+where
+    U: FromObject,
+{
+    match *doc {
+        Document::Ok {
+            ref data,
+            ref included,
+            ..
+        } => {
+            let resolver = Resolver::new(data, included);
+
+            match *data {
+                Data::Member(ref data) => match **data {
+                    Some(ref object) => U::from_object(object, &resolver).map(Some),
+                    None => Ok(None),
+                },
+                Data::Collection(_) => Err(Error::invalid_member(
+                    "/data",
+                    "expected a single resource object",
+                )),
+            }
+        }
+        Document::Err { .. } => {
+            let e = Error::from("Document contains one or more error(s)");
+            Err(e)
+        }
+        Document::Meta { .. } => Err(Error::missing_member("/data")),
+    }
+}
+
+/// Interpret the primary data of a `Document<Object>` as a collection of type `U`.
+///
+/// Returns an error if the primary data is a single resource or the document contains
+/// errors.
+pub fn resolve_many<U, C>(doc: &Document<Object>) -> Result<C, Error>
+where
+    U: FromObject,
+    C: FromIterator<U>,
+{
+    match *doc {
+        Document::Ok {
+            ref data,
+            ref included,
+            ..
+        } => {
+            let resolver = Resolver::new(data, included);
+
+            match *data {
+                Data::Collection(ref data) => data
+                    .iter()
+                    .map(|object| U::from_object(object, &resolver))
+                    .collect(),
+                Data::Member(_) => Err(Error::invalid_member(
+                    "/data",
+                    "expected a collection of resource objects",
+                )),
+            }
+        }
+        Document::Err { .. } => {
+            let e = Error::from("Document contains one or more error(s)");
+            Err(e)
+        }
+        Document::Meta { .. } => Err(Error::missing_member("/data")),
+    }
+}
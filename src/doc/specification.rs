@@ -9,7 +9,11 @@ use serde::{
     Deserialize, Serialize,
 };
 
-use crate::{error::Error, value::Map};
+use crate::{
+    doc::Link,
+    error::Error,
+    value::{Map, Set},
+};
 
 /// Information about this implementation of the specification.
 ///
@@ -20,6 +24,14 @@ use crate::{error::Error, value::Map};
 #[non_exhaustive]
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct JsonApi {
+    /// URIs of the extensions that have been applied to the document. If this value of
+    /// this field is empty, it will not be serialized. For more information, check out
+    /// the *[extensions]* section of the JSON API specification.
+    ///
+    /// [extensions]: https://jsonapi.org/format/1.1/#extensions
+    #[serde(default, skip_serializing_if = "Set::is_empty")]
+    pub ext: Set<Link>,
+
     /// Non-standard meta information. If this value of this field is empty, it will not
     /// be included if the object is serialized. For more information, check out the
     /// *[meta information]* section of the JSON API specification.
@@ -28,8 +40,17 @@ pub struct JsonApi {
     #[serde(default, skip_serializing_if = "Map::is_empty")]
     pub meta: Map,
 
-    /// The latest version of the JSON API specification that is supported by
-    /// this implementation. Defaults to the latest available version.
+    /// URIs of the profiles that have been applied to the document. If this value of
+    /// this field is empty, it will not be serialized. For more information, check out
+    /// the *[profiles]* section of the JSON API specification.
+    ///
+    /// [profiles]: https://jsonapi.org/format/1.1/#profiles
+    #[serde(default, skip_serializing_if = "Set::is_empty")]
+    pub profile: Set<Link>,
+
+    /// The version of the JSON API specification that the document conforms to. If
+    /// this member is absent when the object is deserialized, version 1.0 is assumed.
+    #[serde(default)]
     pub version: Version,
 }
 
@@ -49,29 +70,77 @@ impl JsonApi {
     pub fn new(version: Version) -> Self {
         JsonApi {
             version,
+            ext: Default::default(),
             meta: Default::default(),
+            profile: Default::default(),
+        }
+    }
+
+    /// Returns the lowest version of the specification that can represent `self`.
+    ///
+    /// The `ext` and `profile` members were introduced in version 1.1. If either of
+    /// them is present, `Version::V1_1` is returned regardless of the value of the
+    /// `version` field.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::doc::{JsonApi, Version};
+    ///
+    /// let mut jsonapi = JsonApi::default();
+    /// assert_eq!(jsonapi.required_version(), Version::V1);
+    ///
+    /// jsonapi.ext.insert("https://jsonapi.org/ext/atomic".parse()?);
+    /// assert_eq!(jsonapi.required_version(), Version::V1_1);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn required_version(&self) -> Version {
+        if self.ext.is_empty() && self.profile.is_empty() {
+            self.version
+        } else {
+            self.version.max(Version::V1_1)
         }
     }
 }
 
 /// The version of the specification.
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Version {
     /// Version 1.0
+    #[default]
     V1,
+
+    /// Version 1.1
+    V1_1,
 }
 
-impl Default for Version {
-    fn default() -> Self {
-        Version::V1
+impl Version {
+    /// Returns the version as it appears in the `version` member of a
+    /// [JSON API object].
+    ///
+    /// [JSON API object]: https://goo.gl/hZUcEt
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Version::V1 => "1.0",
+            Version::V1_1 => "1.1",
+        }
     }
 }
 
 impl Display for Version {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        f.write_str(match *self {
-            Version::V1 => "1.0",
-        })
+        f.write_str(self.as_str())
     }
 }
 
@@ -81,6 +150,7 @@ impl FromStr for Version {
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         match value {
             "1.0" => Ok(Version::V1),
+            "1.1" => Ok(Version::V1_1),
             v => Err(Error::unsupported_version(v)),
         }
     }
@@ -101,8 +171,6 @@ impl Serialize for Version {
     where
         S: Serializer,
     {
-        serializer.serialize_str(match *self {
-            Version::V1 => "1.0",
-        })
+        serializer.serialize_str(self.as_str())
     }
 }
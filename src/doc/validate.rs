@@ -0,0 +1,313 @@
+//! Validate that a document complies with the JSON API specification.
+//!
+//! Deserializing a document only checks its structure. The [`document`] function in this
+//! module checks the rules of the specification that span multiple members of a
+//! document, such as *[full linkage]* of included resources.
+//!
+//! # Example
+//!
+//! ```
+//! # extern crate json_api;
+//! #
+//! # use json_api::Error;
+//! #
+//! # fn example() -> Result<(), Error> {
+//! use json_api::doc::{validate, Document, Object};
+//!
+//! let doc: Document<Object> = serde_json::from_str(r#"{
+//!     "data": { "type": "posts", "id": "1" },
+//!     "included": [{ "type": "users", "id": "2" }]
+//! }"#)?;
+//!
+//! let violations = validate::document(&doc);
+//!
+//! assert_eq!(violations.len(), 1);
+//! assert_eq!(violations[0].pointer, "/included/0");
+//! #
+//! # Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! # example().unwrap();
+//! # }
+//! ```
+//!
+//! [`document`]: ./fn.document.html
+//! [full linkage]: http://jsonapi.org/format/#document-compound-documents
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter},
+};
+
+use http::StatusCode;
+
+use crate::{
+    doc::{
+        Data, Document, ErrorObject, ErrorSource, Identifier, Object, PrimaryData, Relationship,
+    },
+    value::{Key, Map, Set},
+};
+
+/// A rule of the specification that is broken by a document.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Violation {
+    /// The rule that is broken.
+    pub kind: ViolationKind,
+
+    /// A JSON pointer to the member of the document that breaks the rule.
+    pub pointer: String,
+}
+
+impl Violation {
+    fn new(kind: ViolationKind, pointer: String) -> Self {
+        Violation { kind, pointer }
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, r#"{} at "{}""#, self.kind, self.pointer)
+    }
+}
+
+impl From<Violation> for ErrorObject {
+    fn from(violation: Violation) -> Self {
+        let mut error = ErrorObject::new(Some(StatusCode::BAD_REQUEST));
+
+        error.code = Some(violation.kind.code().to_owned());
+        error.detail = Some(violation.kind.to_string());
+        error.source = Some(ErrorSource::new(None, Some(violation.pointer)));
+        error
+    }
+}
+
+/// The rules of the specification that are checked by [`document`].
+///
+/// [`document`]: ./fn.document.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ViolationKind {
+    /// More than one resource object has the same `type` and `id`.
+    DuplicateResource(Key, String),
+
+    /// A field is used as both an attribute and a relationship.
+    FieldConflict(Key),
+
+    /// An attribute or relationship uses a reserved name, such as `type` or `id`.
+    ReservedMember(Key),
+
+    /// An included resource is not referenced by the primary data or by another included
+    /// resource.
+    UnlinkedResource,
+}
+
+impl ViolationKind {
+    /// Returns an application-specific code that identifies the rule. This value is
+    /// used as the `code` of an `ErrorObject`.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            ViolationKind::DuplicateResource(..) => "duplicate-resource",
+            ViolationKind::FieldConflict(_) => "field-conflict",
+            ViolationKind::ReservedMember(_) => "reserved-member",
+            ViolationKind::UnlinkedResource => "unlinked-resource",
+        }
+    }
+}
+
+impl Display for ViolationKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            ViolationKind::DuplicateResource(ref kind, ref id) => write!(
+                f,
+                r#"more than one resource object has the type "{}" and id "{}""#,
+                kind, id
+            ),
+            ViolationKind::FieldConflict(ref name) => write!(
+                f,
+                r#"the field "{}" is both an attribute and a relationship"#,
+                name
+            ),
+            ViolationKind::ReservedMember(ref name) => {
+                write!(f, r#"the member name "{}" is reserved"#, name)
+            }
+            ViolationKind::UnlinkedResource => write!(
+                f,
+                "the included resource is not referenced by the primary data or another \
+                 included resource"
+            ),
+        }
+    }
+}
+
+/// The members of primary data that are checked by [`document`].
+///
+/// [`document`]: ./fn.document.html
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Members<'a> {
+    pub(crate) kind: &'a Key,
+    pub(crate) id: Option<&'a str>,
+    pub(crate) lid: Option<&'a str>,
+    pub(crate) attributes: Option<&'a Map>,
+    pub(crate) relationships: Option<&'a Map<Key, Relationship>>,
+}
+
+/// Returns the violations of the specification in `doc`.
+///
+/// A document that contains errors has no primary data, so only documents that do not
+/// contain errors are checked. Full linkage is checked without regard to sparse
+/// fieldsets. If relationships are omitted because of a sparse fieldset, the included
+/// resources that they reference are reported as unlinked.
+pub fn document<T: PrimaryData>(doc: &Document<T>) -> Vec<Violation> {
+    let (data, included) = match *doc {
+        Document::Ok {
+            ref data,
+            ref included,
+            ..
+        } => (data, included),
+        Document::Err { .. } | Document::Meta { .. } => return Vec::new(),
+    };
+
+    let pointers = match *data {
+        Data::Member(_) => vec!["/data".to_owned()],
+        Data::Collection(ref data) => (0..data.len())
+            .map(|index| format!("/data/{}", index))
+            .collect(),
+    };
+
+    let included_pointers = (0..included.len())
+        .map(|index| format!("/included/{}", index))
+        .collect::<Vec<_>>();
+
+    let items = members(data)
+        .into_iter()
+        .zip(&pointers)
+        .chain(included.iter().map(Object::members).zip(&included_pointers));
+
+    let mut violations = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (item, pointer) in items {
+        check_fields(pointer, &item, &mut violations);
+
+        // Resource identifiers in the primary data are distinct from the resource
+        // objects that they reference.
+        let is_object = item.attributes.is_some();
+
+        if let Some(id) = item.id {
+            if !seen.insert((is_object, &**item.kind, id)) {
+                let kind = ViolationKind::DuplicateResource(item.kind.clone(), id.to_owned());
+                violations.push(Violation::new(kind, pointer.clone()));
+            }
+        }
+    }
+
+    for index in unlinked(data, included) {
+        let pointer = included_pointers[index].clone();
+        violations.push(Violation::new(ViolationKind::UnlinkedResource, pointer));
+    }
+
+    violations
+}
+
+fn check_fields(pointer: &str, item: &Members<'_>, violations: &mut Vec<Violation>) {
+    if let Some(attributes) = item.attributes {
+        for key in attributes.keys() {
+            if let "id" | "type" | "links" | "relationships" = &**key {
+                let kind = ViolationKind::ReservedMember(key.clone());
+                let pointer = format!("{}/attributes/{}", pointer, key);
+
+                violations.push(Violation::new(kind, pointer));
+            }
+        }
+    }
+
+    if let Some(relationships) = item.relationships {
+        for key in relationships.keys() {
+            let pointer = format!("{}/relationships/{}", pointer, key);
+
+            if let "id" | "type" = &**key {
+                let kind = ViolationKind::ReservedMember(key.clone());
+                violations.push(Violation::new(kind, pointer));
+            } else if item.attributes.is_some_and(|attrs| attrs.contains_key(key)) {
+                let kind = ViolationKind::FieldConflict(key.clone());
+                violations.push(Violation::new(kind, pointer));
+            }
+        }
+    }
+}
+
+/// Returns the indices of the included resources that can not be reached from the
+/// primary data by following resource linkage.
+pub(crate) fn unlinked<T: PrimaryData>(data: &Data<T>, included: &Set<Object>) -> Vec<usize> {
+    let data = members(data);
+    let included = included.iter().map(Object::members).collect::<Vec<_>>();
+    let mut by_id = HashMap::new();
+    let mut by_lid = HashMap::new();
+
+    for (index, item) in included.iter().enumerate() {
+        if let Some(id) = item.id {
+            by_id.insert((&**item.kind, id), index);
+        }
+
+        if let Some(lid) = item.lid {
+            by_lid.insert((&**item.kind, lid), index);
+        }
+    }
+
+    let find = |item: &Members| match (item.id, item.lid) {
+        (Some(id), _) => by_id.get(&(&**item.kind, id)).cloned(),
+        (None, Some(lid)) => by_lid.get(&(&**item.kind, lid)).cloned(),
+        (None, None) => None,
+    };
+
+    let mut linked = vec![false; included.len()];
+    let mut queue = Vec::new();
+
+    for item in &data {
+        match item.relationships {
+            Some(relationships) => {
+                queue.extend(linkage(relationships).filter_map(|ident| find(&ident)))
+            }
+            None => queue.extend(find(item)),
+        }
+    }
+
+    while let Some(index) = queue.pop() {
+        if linked[index] {
+            continue;
+        }
+
+        linked[index] = true;
+
+        if let Some(relationships) = included[index].relationships {
+            queue.extend(linkage(relationships).filter_map(|ident| find(&ident)));
+        }
+    }
+
+    linked
+        .iter()
+        .enumerate()
+        .filter(|&(_, linked)| !linked)
+        .map(|(index, _)| index)
+        .collect()
+}
+
+fn members<T: PrimaryData>(data: &Data<T>) -> Vec<Members<'_>> {
+    match *data {
+        Data::Member(ref data) => data.iter().map(T::members).collect(),
+        Data::Collection(ref data) => data.iter().map(T::members).collect(),
+    }
+}
+
+/// Returns the resource identifiers of every relationship in `relationships`.
+fn linkage(relationships: &Map<Key, Relationship>) -> impl Iterator<Item = Members<'_>> {
+    relationships
+        .values()
+        .flat_map(|rel| match rel.data {
+            Some(Data::Member(ref data)) => data.iter().collect(),
+            Some(Data::Collection(ref data)) => data.iter().collect(),
+            None => Vec::new(),
+        })
+        .map(Identifier::members)
+}
@@ -6,9 +6,12 @@ use std::str::Utf8Error;
 use error_chain::error_chain;
 use http::status::InvalidStatusCode as InvalidStatusCodeError;
 use http::uri::InvalidUri as InvalidUriError;
+use http::StatusCode;
 use serde_json::Error as JsonError;
 use serde_qs::Error as QueryError;
 
+use crate::doc::{ErrorObject, ErrorSource};
+
 error_chain! {
     foreign_links {
         InvalidStatusCode(InvalidStatusCodeError);
@@ -25,16 +28,51 @@ error_chain! {
             display("TODO")
         }
 
+        DepthLimitExceeded(limit: usize) {
+            description("A document exceeds the maximum relationship depth.")
+            display("relationships exceed the maximum depth of {}", limit)
+        }
+
+        IncludeLimitExceeded(name: String, limit: usize) {
+            description("The include query parameter exceeds a limit.")
+            display(r#"the {} of the "include" query parameter exceeds the limit of {}"#, name, limit)
+        }
+
+        InvalidMember(pointer: String, reason: String) {
+            description("A member of a document could not be interpreted.")
+            display(r#"invalid member at "{}": {}"#, pointer, reason)
+        }
+
+        InvalidParameter(parameter: String, reason: String) {
+            description("A query parameter could not be interpreted.")
+            display(r#"invalid query parameter "{}": {}"#, parameter, reason)
+        }
+
         MissingField(name: String) {
             description("A struct was built without a required field.")
             display(r#"missing required field "{}""#, name)
         }
 
+        MissingMember(pointer: String) {
+            description("A document is missing a required member.")
+            display(r#"missing required member at "{}""#, pointer)
+        }
+
+        NotAcceptable(value: String) {
+            description("None of the acceptable media types are supported.")
+            display(r#"none of the media types in "{}" are supported"#, value)
+        }
+
         PayloadTooLarge(size: u64) {
             description("The payload is too large")
             display(r#"payload exceeds limit of {} B"#, size)
         }
 
+        UnsupportedMediaType(value: String) {
+            description("The media type of a request document is not supported.")
+            display(r#"the media type "{}" is not supported"#, value)
+        }
+
         UnsupportedVersion(version: String) {
             description("The specified version of is not \
                          supported by this implementation.")
@@ -45,15 +83,109 @@ error_chain! {
 }
 
 impl Error {
+    /// Returns the source of the error in a request document or query string, if it is
+    /// known.
+    pub fn error_source(&self) -> Option<ErrorSource> {
+        match *self.kind() {
+            ErrorKind::InvalidMember(ref pointer, _) | ErrorKind::MissingMember(ref pointer) => {
+                Some(ErrorSource::new(None, Some(pointer.clone())))
+            }
+            ErrorKind::InvalidParameter(ref parameter, _) => {
+                Some(ErrorSource::parameter(parameter))
+            }
+            ErrorKind::IncludeLimitExceeded(..) => Some(ErrorSource::parameter("include")),
+            _ => None,
+        }
+    }
+
+    /// Returns the HTTP status code that best describes the error if it is returned in
+    /// response to a request.
+    ///
+    /// Errors that are caused by the server map to `500 Internal Server Error`.
+    pub fn status(&self) -> StatusCode {
+        match *self.kind() {
+            ErrorKind::IncludeLimitExceeded(..)
+            | ErrorKind::InvalidMember(..)
+            | ErrorKind::InvalidParameter(..)
+            | ErrorKind::MissingMember(_)
+            | ErrorKind::Query(_) => StatusCode::BAD_REQUEST,
+            ErrorKind::NotAcceptable(_) => StatusCode::NOT_ACCEPTABLE,
+            ErrorKind::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ErrorKind::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Returns `true` if the error was caused by the request, rather than by the
+    /// server.
+    pub fn is_client_error(&self) -> bool {
+        self.status().is_client_error()
+    }
+
+    pub fn depth_limit_exceeded(limit: usize) -> Self {
+        Self::from(ErrorKind::DepthLimitExceeded(limit))
+    }
+
+    pub fn include_limit_exceeded(name: &str, limit: usize) -> Self {
+        Self::from(ErrorKind::IncludeLimitExceeded(name.to_owned(), limit))
+    }
+
+    pub fn invalid_member(pointer: &str, reason: &str) -> Self {
+        Self::from(ErrorKind::InvalidMember(
+            pointer.to_owned(),
+            reason.to_owned(),
+        ))
+    }
+
+    pub fn invalid_parameter(parameter: &str, reason: &str) -> Self {
+        Self::from(ErrorKind::InvalidParameter(
+            parameter.to_owned(),
+            reason.to_owned(),
+        ))
+    }
+
     pub fn missing_field(name: &str) -> Self {
         Self::from(ErrorKind::MissingField(name.to_owned()))
     }
 
+    pub fn missing_member(pointer: &str) -> Self {
+        Self::from(ErrorKind::MissingMember(pointer.to_owned()))
+    }
+
+    pub fn not_acceptable(value: &str) -> Self {
+        Self::from(ErrorKind::NotAcceptable(value.to_owned()))
+    }
+
     pub fn payload_too_large(size: u64) -> Self {
         Self::from(ErrorKind::PayloadTooLarge(size))
     }
 
+    pub fn unsupported_media_type(value: &str) -> Self {
+        Self::from(ErrorKind::UnsupportedMediaType(value.to_owned()))
+    }
+
     pub fn unsupported_version(version: &str) -> Self {
         Self::from(ErrorKind::UnsupportedVersion(version.to_owned()))
     }
 }
+
+impl<'a> From<&'a Error> for ErrorObject {
+    /// Returns an error object with the [status], the detail, and the source of `error`
+    /// if it is a client error. Otherwise, returns an error object with the status
+    /// `500 Internal Server Error` that does not expose the details of `error`.
+    ///
+    /// [status]: ../error/struct.Error.html#method.status
+    fn from(error: &'a Error) -> Self {
+        let status = error.status();
+
+        if !status.is_client_error() {
+            return ErrorObject::new(Some(status));
+        }
+
+        let mut object = ErrorObject::new(Some(status));
+
+        object.detail = Some(error.to_string());
+        object.source = error.error_source();
+        object
+    }
+}
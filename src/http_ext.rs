@@ -0,0 +1,144 @@
+//! Handle requests and build responses with the types of the [`http`] crate.
+//!
+//! The functions in this module implement the parts of the JSON API specification
+//! that are independent of a web framework. Validate the headers of a request with
+//! [`negotiate`], parse its query string with [`query`], decode its document with
+//! [`decode`], and build a response with [`response`] or [`error_response`].
+//!
+//! # Example
+//!
+//! ```
+//! # extern crate json_api;
+//! #
+//! # use json_api::Error;
+//! #
+//! # fn example() -> Result<(), Error> {
+//! use json_api::doc::NewObject;
+//! use json_api::http::{Request, StatusCode};
+//! use json_api::{http_ext, Value};
+//!
+//! let req = Request::post("/posts?include=author")
+//!     .header("Content-Type", "application/vnd.api+json")
+//!     .body(r#"{"data":{"type":"posts","attributes":{"title":"Hello"}}}"#)
+//!     .unwrap();
+//!
+//! http_ext::negotiate(req.headers(), &[])?;
+//!
+//! let query = http_ext::query(req.uri())?;
+//! let post = http_ext::decode::<NewObject, Value, _>(&req)?;
+//!
+//! assert_eq!(query, json_api::query::from_str("include=author")?);
+//! assert_eq!(post, json_api::from_str::<NewObject, Value>(req.body())?);
+//!
+//! let resp = http_ext::error_response(&Error::payload_too_large(1024));
+//! assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+//! #
+//! # Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! # example().unwrap();
+//! # }
+//! ```
+//!
+//! [`http`]: https://docs.rs/http/1
+//! [`negotiate`]: ./fn.negotiate.html
+//! [`query`]: ./fn.query.html
+//! [`decode`]: ./fn.decode.html
+//! [`response`]: ./fn.response.html
+//! [`error_response`]: ./fn.error_response.html
+
+use http::header::{HeaderMap, HeaderValue, ACCEPT, CONTENT_TYPE};
+use http::{Request, Response, StatusCode, Uri};
+use serde::de::DeserializeOwned;
+
+use crate::doc::{self, ErrorObject, PrimaryData};
+use crate::error::Error;
+use crate::media_type::{self, MEDIA_TYPE};
+use crate::query::{self, Query};
+use crate::view::Render;
+
+pub use crate::media_type::Negotiated;
+
+/// Checks the `Content-Type` and `Accept` headers in `headers` with the rules of
+/// [`media_type::negotiate`]. The extensions in `extensions` are supported.
+///
+/// [`media_type::negotiate`]: ../media_type/fn.negotiate.html
+pub fn negotiate(headers: &HeaderMap, extensions: &[&str]) -> Result<Negotiated, Error> {
+    // A value that is not visible ASCII can not be a supported media type.
+    let header = |name| {
+        headers
+            .get(name)
+            .map(|value: &HeaderValue| value.to_str().unwrap_or_default())
+    };
+
+    media_type::negotiate(header(CONTENT_TYPE), header(ACCEPT), extensions)
+}
+
+/// Parses the query string of `uri`. A URI without a query string has the default
+/// query.
+pub fn query(uri: &Uri) -> Result<Query, Error> {
+    match uri.query() {
+        Some(value) => query::from_str(value),
+        None => Ok(Default::default()),
+    }
+}
+
+/// Checks the headers of `req` with [`negotiate`] and interprets its body as a
+/// `Document<T>` that contains a type `U`.
+///
+/// A body that is not valid JSON is reported as an invalid member at the root of the
+/// document, so it maps to `400 Bad Request` rather than a server error.
+///
+/// [`negotiate`]: ./fn.negotiate.html
+pub fn decode<T, U, B>(req: &Request<B>) -> Result<U, Error>
+where
+    T: PrimaryData,
+    U: DeserializeOwned,
+    B: AsRef<[u8]>,
+{
+    negotiate(req.headers(), &[])?;
+    doc::from_body::<T, U>(req.body().as_ref())
+}
+
+/// Renders `value` as a `Document<D>` and returns a `200 OK` response that contains it.
+///
+/// The `Content-Type` header of the response is the JSON API media type.
+pub fn response<T, D>(value: T, query: Option<&Query>) -> Result<Response<Vec<u8>>, Error>
+where
+    T: Render<D>,
+    D: PrimaryData,
+{
+    let body = doc::to_vec(value, query)?;
+    Ok(with_body(StatusCode::OK, body))
+}
+
+/// Returns a response that contains an error document that describes `error`, either
+/// an `&Error` or an `ErrorObject`.
+///
+/// The status of the response is the status of the error object. Errors caused by the
+/// server do not expose their details. If the document can not be serialized, the
+/// response has an empty body and no `Content-Type`.
+pub fn error_response<E: Into<ErrorObject>>(error: E) -> Response<Vec<u8>> {
+    let object = error.into();
+    let status = object.status.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+    match doc::error_to_vec(object) {
+        Ok(body) => with_body(status, body),
+        Err(_) => {
+            let mut resp = Response::new(Vec::new());
+            *resp.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+            resp
+        }
+    }
+}
+
+fn with_body(status: StatusCode, body: Vec<u8>) -> Response<Vec<u8>> {
+    let mut resp = Response::new(body);
+
+    *resp.status_mut() = status;
+    resp.headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(MEDIA_TYPE));
+
+    resp
+}
@@ -1,6 +1,7 @@
 //! Idiomatic types for building a robust JSON API.
 
 mod resource;
+mod schema;
 
 mod sealed {
     /// Private trait used to prevent marker traits from being implemented
@@ -10,6 +11,8 @@ mod sealed {
 
 pub mod doc;
 pub mod error;
+pub mod http_ext;
+pub mod media_type;
 pub mod query;
 pub mod value;
 pub mod view;
@@ -24,8 +27,17 @@ pub use doc::{
 };
 #[doc(inline)]
 pub use error::Error;
+#[cfg(feature = "derive")]
+pub use json_api_derive::Resource;
 pub use resource::Resource;
+pub use schema::Schema;
 #[doc(inline)]
 pub use value::{from_value, to_value, Value};
 
 pub use http;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::resource::{render_has_many, render_has_one};
+    pub use crate::schema::{related_schema, unknown_schema};
+}
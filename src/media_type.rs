@@ -0,0 +1,209 @@
+//! Negotiate the JSON API media type of requests and responses.
+//!
+//! The specification requires a server to respond with `415 Unsupported Media Type` if
+//! the `Content-Type` of a request document has media type parameters, and with `406
+//! Not Acceptable` if every instance of the JSON API media type in the `Accept` header
+//! of a request has media type parameters. The `ext` and `profile` parameters are the
+//! only exceptions. A server must reject the extensions that it does not support, but
+//! can ignore profiles.
+//!
+//! For more information, check out the *[content negotiation]* section of the JSON API
+//! specification.
+//!
+//! # Example
+//!
+//! ```
+//! # extern crate json_api;
+//! #
+//! # use json_api::Error;
+//! #
+//! # fn example() -> Result<(), Error> {
+//! use json_api::media_type;
+//!
+//! let atomic = "https://jsonapi.org/ext/atomic";
+//! let value = format!(r#"application/vnd.api+json; ext="{}""#, atomic);
+//!
+//! assert!(media_type::content_type(Some(&value), &[]).is_err());
+//! assert_eq!(media_type::content_type(Some(&value), &[atomic])?.ext, vec![atomic]);
+//!
+//! let accept = "application/vnd.api+json; charset=utf-8, application/vnd.api+json";
+//! assert!(media_type::accept(Some(accept), &[]).is_ok());
+//! #
+//! # Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! # example().unwrap();
+//! # }
+//! ```
+//!
+//! [content negotiation]: https://jsonapi.org/format/#content-negotiation
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::error::Error;
+
+/// The JSON API media type.
+pub const MEDIA_TYPE: &str = "application/vnd.api+json";
+
+/// An instance of the JSON API media type and its `ext` and `profile` parameters.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MediaType {
+    /// The URIs of the extensions that are applied.
+    pub ext: Vec<String>,
+
+    /// The URIs of the profiles that are applied.
+    pub profile: Vec<String>,
+}
+
+/// The media types that were negotiated for a request. Returned by [`negotiate`].
+///
+/// [`negotiate`]: ./fn.negotiate.html
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Negotiated {
+    /// The media type of the request document.
+    pub content_type: MediaType,
+
+    /// The media type that the client accepts for the response document.
+    pub accept: MediaType,
+}
+
+impl MediaType {
+    /// Returns a new `MediaType` without extensions or profiles.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Interprets the parameters of an instance of the JSON API media type. Returns
+    /// `None` if a parameter is not supported.
+    fn from_params<'a, I>(params: I, extensions: &[&str]) -> Option<Self>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let mut media_type = MediaType::new();
+
+        for param in params {
+            let (name, value) = param.split_once('=')?;
+            let uris = value
+                .trim()
+                .trim_matches('"')
+                .split_whitespace()
+                .map(str::to_owned);
+
+            match name.trim().to_ascii_lowercase().as_str() {
+                "ext" => media_type.ext.extend(uris),
+                "profile" => media_type.profile.extend(uris),
+                _ => return None,
+            }
+        }
+
+        if media_type
+            .ext
+            .iter()
+            .all(|uri| extensions.contains(&&**uri))
+        {
+            Some(media_type)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for MediaType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(MEDIA_TYPE)?;
+
+        if !self.ext.is_empty() {
+            write!(f, r#"; ext="{}""#, self.ext.join(" "))?;
+        }
+
+        if !self.profile.is_empty() {
+            write!(f, r#"; profile="{}""#, self.profile.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks the value of the `Content-Type` header of a request document.
+///
+/// Returns the media type of the request document if it is the JSON API media type
+/// and the extensions that it applies are in `extensions`. A missing header is
+/// interpreted as the JSON API media type without parameters. Otherwise, returns an
+/// error that maps to `415 Unsupported Media Type`.
+pub fn content_type(value: Option<&str>, extensions: &[&str]) -> Result<MediaType, Error> {
+    let value = match value {
+        Some(value) => value,
+        None => return Ok(MediaType::new()),
+    };
+
+    let mut parts = value.split(';').map(str::trim);
+
+    parts
+        .next()
+        .filter(|essence| essence.eq_ignore_ascii_case(MEDIA_TYPE))
+        .and_then(|_| MediaType::from_params(parts, extensions))
+        .ok_or_else(|| Error::unsupported_media_type(value))
+}
+
+/// Checks the value of the `Accept` header of a request.
+///
+/// Returns the first instance of the JSON API media type that can be used for the
+/// response. If the header does not contain the JSON API media type, a response
+/// without parameters is assumed to be acceptable. Returns an error that maps to `406
+/// Not Acceptable` if every instance of the JSON API media type has a parameter that
+/// is not supported.
+pub fn accept(value: Option<&str>, extensions: &[&str]) -> Result<MediaType, Error> {
+    let value = match value {
+        Some(value) => value,
+        None => return Ok(MediaType::new()),
+    };
+
+    let mut requested = false;
+
+    for item in value.split(',') {
+        let mut parts = item.split(';').map(str::trim);
+
+        if !parts
+            .next()
+            .is_some_and(|essence| essence.eq_ignore_ascii_case(MEDIA_TYPE))
+        {
+            continue;
+        }
+
+        // The parameters that follow the weight are accept extensions rather than
+        // media type parameters.
+        let params = parts.take_while(|param| {
+            let name = param.split('=').next().unwrap_or_default();
+            !name.trim().eq_ignore_ascii_case("q")
+        });
+
+        match MediaType::from_params(params, extensions) {
+            Some(media_type) => return Ok(media_type),
+            None => requested = true,
+        }
+    }
+
+    if requested {
+        Err(Error::not_acceptable(value))
+    } else {
+        Ok(MediaType::new())
+    }
+}
+
+/// Checks the values of the `Content-Type` and `Accept` headers of a request with the
+/// rules of [`content_type`] and [`accept`]. The extensions in `extensions` are
+/// supported.
+///
+/// [`content_type`]: ./fn.content_type.html
+/// [`accept`]: ./fn.accept.html
+pub fn negotiate(
+    content_type: Option<&str>,
+    accept: Option<&str>,
+    extensions: &[&str],
+) -> Result<Negotiated, Error> {
+    Ok(Negotiated {
+        content_type: self::content_type(content_type, extensions)?,
+        accept: self::accept(accept, extensions)?,
+    })
+}
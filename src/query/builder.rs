@@ -2,7 +2,7 @@ use std::mem;
 
 use crate::{
     error::Error,
-    query::{Direction, Page, Query, Sort},
+    query::{Direction, Limits, Page, Query, Sort},
     value::{Key, Map, Path, Set, Value},
 };
 
@@ -13,6 +13,7 @@ pub struct Builder {
     fields: Vec<(String, Vec<String>)>,
     filter: Vec<(String, Value)>,
     include: Vec<String>,
+    limits: Limits,
     page: Option<Page>,
     sort: Vec<(String, Direction)>,
 }
@@ -20,7 +21,7 @@ pub struct Builder {
 impl Builder {
     /// Attempt to construct a new query from the previously supplied values.
     pub fn build(&mut self) -> Result<Query, Error> {
-        Ok(Query {
+        let query = Query {
             sort: {
                 self.sort
                     .drain(..)
@@ -57,7 +58,10 @@ impl Builder {
                     .collect::<Result<Set<Path>, Error>>()?
             },
             page: mem::replace(&mut self.page, None),
-        })
+        };
+
+        self.limits.check(&query)?;
+        Ok(query)
     }
 
     pub fn fields<I, K, V>(&mut self, key: K, iter: I) -> &mut Self
@@ -93,11 +97,39 @@ impl Builder {
         self
     }
 
+    pub fn limits(&mut self, limits: Limits) -> &mut Self {
+        self.limits = limits;
+        self
+    }
+
     pub fn page(&mut self, number: u64, size: Option<u64>) -> &mut Self {
         self.page = Some(Page::new(number, size));
         self
     }
 
+    pub fn page_offset(&mut self, offset: u64, limit: Option<u64>) -> &mut Self {
+        self.page = Some(Page::offset(offset, limit));
+        self
+    }
+
+    pub fn page_size(&mut self, size: u64) -> &mut Self {
+        self.page = Some(Page::size_only(size));
+        self
+    }
+
+    pub fn page_cursor(
+        &mut self,
+        after: Option<&str>,
+        before: Option<&str>,
+        size: Option<u64>,
+    ) -> &mut Self {
+        let after = after.map(str::to_owned);
+        let before = before.map(str::to_owned);
+
+        self.page = Some(Page::cursor(after, before, size));
+        self
+    }
+
     pub fn sort<F>(&mut self, field: F, direction: Direction) -> &mut Self
     where
         F: Into<String>,
@@ -0,0 +1,398 @@
+use std::iter;
+
+use crate::{
+    error::Error,
+    value::{Key, Map, Path, Value},
+};
+
+/// A filter expression parsed from the `filter` query parameter.
+///
+/// Each member of the `filter` query parameter is interpreted as a condition on the
+/// field with the same path. Conditions are combined with [`And`], unless they are
+/// nested in the reserved `and` or `or` members.
+///
+/// | Query string                               | Expression                         |
+/// |--------------------------------------------|------------------------------------|
+/// | `filter[name]=Alfred`                      | `name = "Alfred"`                  |
+/// | `filter[name]=Alfred,Bruce`                | `name IN ("Alfred", "Bruce")`      |
+/// | `filter[age][gt]=30`                       | `age > 30`                         |
+/// | `filter[age][gte]=30&filter[age][lt]=40`   | `age >= 30 AND age < 40`           |
+/// | `filter[deleted-at][null]=true`            | `deleted-at IS NULL`               |
+/// | `filter[or][0][name]=Alfred&filter[or][1][age][gt]=30` | `name = "Alfred" OR age > 30` |
+///
+/// The supported operators are `eq`, `ne`, `lt`, `lte`, `gt`, `gte`, `in`, `like`, and
+/// `null`. Members nested in `and` or `or` must be member names rather than paths.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::query::{self, Filter, Operator};
+///
+/// let query = query::from_str("filter[age][gt]=30&filter[users.name]=Alfred,Bruce")?;
+/// let filter = query.parse_filter()?;
+///
+/// assert_eq!(
+///     filter,
+///     Filter::And(vec![
+///         Filter::Field("age".parse()?, Operator::Gt("30".into())),
+///         Filter::Field(
+///             "users.name".parse()?,
+///             Operator::In(vec!["Alfred".into(), "Bruce".into()]),
+///         ),
+///     ])
+/// );
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+///
+/// [`And`]: #variant.And
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    /// Matches if all of the contained expressions match. An empty `And` matches
+    /// everything.
+    And(Vec<Filter>),
+
+    /// Matches if any of the contained expressions match.
+    Or(Vec<Filter>),
+
+    /// A condition on the value of the field at the specified path.
+    Field(Path, Operator),
+}
+
+impl Filter {
+    /// Interprets the members of the `filter` query parameter as a filter expression.
+    ///
+    /// If a member cannot be interpreted, the returned error references the query
+    /// parameter that caused it.
+    pub fn from_map(map: &Map<Path, Value>) -> Result<Self, Error> {
+        let iter = map.iter().map(|(path, value)| (path.clone(), value));
+        parse_members(iter, "filter")
+    }
+
+    /// Returns the members of the `filter` query parameter that represent `self`.
+    ///
+    /// Interpreting the returned map with [`from_map`] produces an equivalent
+    /// expression.
+    ///
+    /// [`from_map`]: #method.from_map
+    pub fn to_map(&self) -> Map<Path, Value> {
+        let items = match *self {
+            Filter::And(ref items) => items.iter().collect(),
+            _ => vec![self],
+        };
+
+        let mut map = Map::new();
+        let mut rest = Vec::new();
+
+        for item in items {
+            if !write(item, &mut map) {
+                rest.push(item);
+            }
+        }
+
+        if !rest.is_empty() {
+            let key = reserved("and");
+            let mut values = match map.remove(&key) {
+                Some(Value::Array(values)) => values,
+                _ => Vec::new(),
+            };
+
+            values.extend(rest.into_iter().map(to_value));
+            map.insert(key, Value::Array(values));
+        }
+
+        map
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Filter::And(Vec::new())
+    }
+}
+
+/// A comparison of the value of a field.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operator {
+    /// Equal to the value. Applies to a bare value or the `eq` operator.
+    Eq(Value),
+
+    /// Not equal to the value.
+    Ne(Value),
+
+    /// Less than the value.
+    Lt(Value),
+
+    /// Less than or equal to the value.
+    Lte(Value),
+
+    /// Greater than the value.
+    Gt(Value),
+
+    /// Greater than or equal to the value.
+    Gte(Value),
+
+    /// Equal to any of the values. Applies to a comma separated list of values or the
+    /// `in` operator.
+    In(Vec<Value>),
+
+    /// Matches the pattern.
+    Like(String),
+
+    /// Is `null` if `true`, otherwise is not `null`.
+    Null(bool),
+}
+
+impl Operator {
+    /// Returns the name of the operator as it appears in a query string.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Operator::Eq(_) => "eq",
+            Operator::Ne(_) => "ne",
+            Operator::Lt(_) => "lt",
+            Operator::Lte(_) => "lte",
+            Operator::Gt(_) => "gt",
+            Operator::Gte(_) => "gte",
+            Operator::In(_) => "in",
+            Operator::Like(_) => "like",
+            Operator::Null(_) => "null",
+        }
+    }
+
+    fn parse(name: &str, value: &Value, param: &str) -> Result<Self, Error> {
+        let scalar = || match *value {
+            Value::Array(_) | Value::Object(_) => {
+                Err(Error::invalid_parameter(param, "expected a single value"))
+            }
+            _ => Ok(value.clone()),
+        };
+
+        Ok(match name {
+            "eq" => Operator::Eq(scalar()?),
+            "ne" => Operator::Ne(scalar()?),
+            "lt" => Operator::Lt(scalar()?),
+            "lte" => Operator::Lte(scalar()?),
+            "gt" => Operator::Gt(scalar()?),
+            "gte" => Operator::Gte(scalar()?),
+            "in" => match *value {
+                Value::Array(ref items) => Operator::In(items.clone()),
+                Value::String(ref items) => Operator::In(split(items)),
+                _ => return Err(Error::invalid_parameter(param, "expected a list of values")),
+            },
+            "like" => match *value {
+                Value::String(ref pattern) => Operator::Like(pattern.clone()),
+                _ => return Err(Error::invalid_parameter(param, "expected a pattern")),
+            },
+            "null" => match *value {
+                Value::Bool(flag) => Operator::Null(flag),
+                Value::String(ref flag) if flag == "true" => Operator::Null(true),
+                Value::String(ref flag) if flag == "false" => Operator::Null(false),
+                _ => return Err(Error::invalid_parameter(param, "expected true or false")),
+            },
+            _ => {
+                let reason = format!(r#"unknown operator "{}""#, name);
+                return Err(Error::invalid_parameter(param, &reason));
+            }
+        })
+    }
+
+    fn to_value(&self) -> Value {
+        match *self {
+            Operator::Eq(ref value)
+            | Operator::Ne(ref value)
+            | Operator::Lt(ref value)
+            | Operator::Lte(ref value)
+            | Operator::Gt(ref value)
+            | Operator::Gte(ref value) => value.clone(),
+            Operator::In(ref values) => Value::Array(values.clone()),
+            Operator::Like(ref pattern) => Value::String(pattern.clone()),
+            Operator::Null(flag) => Value::Bool(flag),
+        }
+    }
+}
+
+fn parse_members<'a, I>(iter: I, prefix: &str) -> Result<Filter, Error>
+where
+    I: Iterator<Item = (Path, &'a Value)>,
+{
+    let mut items = Vec::new();
+
+    for (path, value) in iter {
+        let param = format!("{}[{}]", prefix, path);
+
+        match path.first().filter(|_| path.len() == 1).map(|key| &**key) {
+            Some("and") => items.push(Filter::And(parse_nested(value, &param)?)),
+            Some("or") => items.push(Filter::Or(parse_nested(value, &param)?)),
+            _ => items.push(parse_field(path, value, &param)?),
+        }
+    }
+
+    Ok(collapse(items))
+}
+
+fn parse_nested(value: &Value, param: &str) -> Result<Vec<Filter>, Error> {
+    let items = match *value {
+        Value::Array(ref items) => items,
+        _ => {
+            return Err(Error::invalid_parameter(
+                param,
+                "expected a list of filters",
+            ))
+        }
+    };
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let param = format!("{}[{}]", param, index);
+
+            match *item {
+                Value::Object(ref map) => {
+                    let iter = map.iter().map(|(key, value)| (path(key.clone()), value));
+                    parse_members(iter, &param)
+                }
+                _ => Err(Error::invalid_parameter(&param, "expected a filter")),
+            }
+        })
+        .collect()
+}
+
+fn parse_field(path: Path, value: &Value, param: &str) -> Result<Filter, Error> {
+    let op = match *value {
+        Value::Array(ref items) => Operator::In(items.clone()),
+        Value::String(ref items) if items.contains(',') => Operator::In(split(items)),
+        Value::Object(ref ops) if ops.is_empty() => {
+            return Err(Error::invalid_parameter(param, "expected an operator"));
+        }
+        Value::Object(ref ops) => {
+            let items = ops
+                .iter()
+                .map(|(name, value)| {
+                    let param = format!("{}[{}]", param, name);
+                    let op = Operator::parse(name, value, &param)?;
+
+                    Ok(Filter::Field(path.clone(), op))
+                })
+                .collect::<Result<_, Error>>()?;
+
+            return Ok(collapse(items));
+        }
+        _ => Operator::Eq(value.clone()),
+    };
+
+    Ok(Filter::Field(path, op))
+}
+
+/// Returns the only item of `items` or combines them with `Filter::And`.
+fn collapse(mut items: Vec<Filter>) -> Filter {
+    if items.len() == 1 {
+        items.remove(0)
+    } else {
+        Filter::And(items)
+    }
+}
+
+fn split(value: &str) -> Vec<Value> {
+    value
+        .split(',')
+        .map(|item| item.to_owned().into())
+        .collect()
+}
+
+fn path(key: Key) -> Path {
+    iter::once(key).collect()
+}
+
+fn reserved(name: &str) -> Path {
+    path(Key::from_raw(name.to_owned()))
+}
+
+/// Writes `filter` to `map`. Returns `false` if `filter` conflicts with a member that
+/// is already present.
+fn write(filter: &Filter, map: &mut Map<Path, Value>) -> bool {
+    match *filter {
+        Filter::And(ref items) => {
+            let key = reserved("and");
+
+            if map.contains_key(&key) {
+                return false;
+            }
+
+            map.insert(key, Value::Array(items.iter().map(to_value).collect()));
+            true
+        }
+        Filter::Or(ref items) => {
+            let key = reserved("or");
+
+            if map.contains_key(&key) {
+                return false;
+            }
+
+            map.insert(key, Value::Array(items.iter().map(to_value).collect()));
+            true
+        }
+        Filter::Field(ref path, ref op) => {
+            let mut ops = match map.get(path) {
+                None => match *op {
+                    Operator::Eq(Value::String(ref value)) if value.contains(',') => Map::new(),
+                    Operator::Eq(Value::Array(_)) | Operator::Eq(Value::Object(_)) => Map::new(),
+                    Operator::Eq(ref value) => {
+                        map.insert(path.clone(), value.clone());
+                        return true;
+                    }
+                    _ => Map::new(),
+                },
+                Some(Value::Object(ops)) => ops.clone(),
+                Some(value) => {
+                    let mut ops = Map::new();
+                    ops.insert(Key::from_raw("eq".to_owned()), value.clone());
+                    ops
+                }
+            };
+
+            let name = Key::from_raw(op.name().to_owned());
+
+            if ops.contains_key(&name) {
+                return false;
+            }
+
+            ops.insert(name, op.to_value());
+            map.insert(path.clone(), Value::Object(ops));
+            true
+        }
+    }
+}
+
+/// Returns the members that represent `filter` as a nested object of an `and` or `or`
+/// member.
+fn to_value(filter: &Filter) -> Value {
+    let map = match *filter {
+        Filter::And(_) => filter.to_map(),
+        _ => {
+            let mut map = Map::new();
+
+            if !write(filter, &mut map) {
+                unreachable!("writing a filter to an empty map cannot conflict");
+            }
+
+            map
+        }
+    };
+
+    Value::Object(
+        map.into_iter()
+            .map(|(path, value)| (Key::from_raw(path.to_string()), value))
+            .collect(),
+    )
+}
@@ -0,0 +1,91 @@
+use crate::{error::Error, query::Query};
+
+/// Limits on the `include` query parameter and the compound documents that are rendered
+/// for it.
+///
+/// A client can request deeply nested include paths that cause a server to render a
+/// large graph of related resources. A limit of `None` is not enforced. The default
+/// value does not enforce any limits.
+///
+/// The depth and number of include paths are checked when a query is parsed with
+/// [`from_str_with_limits`]. The number of included resources is checked when a
+/// resource is added to a [`Context`] while a document is rendered with [`Limited`].
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::query::{self, Limits};
+///
+/// let limits = Limits {
+///     max_depth: Some(2),
+///     max_paths: Some(4),
+///     max_included: Some(100),
+/// };
+///
+/// assert!(query::from_str_with_limits("include=author.posts", limits).is_ok());
+/// assert!(query::from_str_with_limits("include=author.posts.comments", limits).is_err());
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`from_str_with_limits`]: ./fn.from_str_with_limits.html
+/// [`Context`]: ../view/struct.Context.html
+/// [`Limited`]: ../view/struct.Limited.html
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Limits {
+    /// The maximum number of relationships in a single include path.
+    pub max_depth: Option<usize>,
+
+    /// The maximum number of resources in the `included` member of a rendered document.
+    pub max_included: Option<usize>,
+
+    /// The maximum number of include paths.
+    pub max_paths: Option<usize>,
+}
+
+impl Limits {
+    /// Returns `Limits` that are not enforced.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns an error if the include paths of `query` exceed the maximum depth or
+    /// number of paths.
+    pub fn check(&self, query: &Query) -> Result<(), Error> {
+        if let Some(limit) = self.max_paths {
+            if query.include.len() > limit {
+                return Err(Error::include_limit_exceeded("number of paths", limit));
+            }
+        }
+
+        if let Some(limit) = self.max_depth {
+            if query.include.iter().any(|path| path.len() > limit) {
+                return Err(Error::include_limit_exceeded("depth", limit));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns an error if adding a resource to `count` included resources exceeds the
+    /// maximum number of included resources.
+    pub(crate) fn check_included(&self, count: usize) -> Result<(), Error> {
+        match self.max_included {
+            Some(limit) if count >= limit => Err(Error::include_limit_exceeded(
+                "number of included resources",
+                limit,
+            )),
+            _ => Ok(()),
+        }
+    }
+}
@@ -1,12 +1,17 @@
 //! An API for working with well-known query parameters.
 
 mod builder;
+mod filter;
+mod limits;
 mod page;
 mod sort;
 
+use std::borrow::Cow;
 use std::fmt::{self, Formatter};
+use std::str;
 
-use percent_encoding::{percent_decode, percent_encode, AsciiSet, CONTROLS};
+use indexmap::IndexMap;
+use percent_encoding::{percent_encode, AsciiSet, CONTROLS};
 use serde::{
     de::{Deserializer, MapAccess, Visitor},
     ser::{Serialize, SerializeStruct, Serializer},
@@ -16,11 +21,14 @@ use serde_qs;
 
 use crate::{
     error::Error,
+    resource::Resource,
     value::{Key, Map, Path, Set, Value},
 };
 
 pub use self::{
     builder::Builder,
+    filter::{Filter, Operator},
+    limits::Limits,
     page::Page,
     sort::{Direction, Sort},
 };
@@ -102,6 +110,116 @@ impl Query {
     pub fn builder() -> Builder {
         Default::default()
     }
+
+    /// Interprets the [`filter`] member of `self` as a [`Filter`] expression.
+    ///
+    /// [`filter`]: #structfield.filter
+    /// [`Filter`]: ./enum.Filter.html
+    pub fn parse_filter(&self) -> Result<Filter, Error> {
+        Filter::from_map(&self.filter)
+    }
+
+    /// Replaces the [`filter`] member of `self` with the members that represent
+    /// `filter`.
+    ///
+    /// [`filter`]: #structfield.filter
+    pub fn set_filter(&mut self, filter: &Filter) {
+        self.filter = filter.to_map();
+    }
+
+    /// Returns an error if a path in the [`include`] member of `self` does not match a
+    /// relationship of `T` or a related resource type.
+    ///
+    /// The specification requires a server to respond with `400 Bad Request` if it
+    /// does not support an include path. The returned error references the `include`
+    /// query parameter. If the [`schema`] of `T` is unknown, every path is accepted.
+    ///
+    /// [`include`]: #structfield.include
+    /// [`schema`]: ../trait.Resource.html#method.schema
+    pub fn validate_include<T: Resource>(&self) -> Result<(), Error> {
+        match T::schema() {
+            Some(schema) => schema.validate_include(&self.include),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns an error if a member of a sparse fieldset in the [`fields`] member of
+    /// `self` is not an attribute or relationship of the resource type that it applies
+    /// to.
+    ///
+    /// The fieldsets of `T` and the resource types that are related to `T` are checked.
+    /// The returned error references the query parameter of the fieldset, for example
+    /// `fields[posts]`. If the [`schema`] of `T` is unknown, every fieldset is accepted.
+    ///
+    /// [`fields`]: #structfield.fields
+    /// [`schema`]: ../trait.Resource.html#method.schema
+    pub fn validate_fields<T: Resource>(&self) -> Result<(), Error> {
+        match T::schema() {
+            Some(schema) => schema.validate_fields(&self.fields),
+            None => Ok(()),
+        }
+    }
+
+    /// Adds the relationships that are required by the [`include`] member of `self` to
+    /// the sparse fieldsets in the [`fields`] member of `self`.
+    ///
+    /// By default, a relationship that is not in the sparse fieldset of a resource type
+    /// is not rendered, even if the resources that it references are included. Calling
+    /// this method before rendering keeps the included resources linked to the primary
+    /// data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[macro_use]
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # struct User(u64);
+    /// #
+    /// # resource!(User, |&self| {
+    /// #     kind "users";
+    /// #     id self.0;
+    /// # });
+    /// #
+    /// struct Post {
+    ///     id: u64,
+    ///     title: String,
+    ///     author: Option<User>,
+    /// }
+    ///
+    /// resource!(Post, |&self| {
+    ///     kind "posts";
+    ///     id self.id;
+    ///
+    ///     attrs title;
+    ///     has_one author;
+    /// });
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::query;
+    ///
+    /// let mut query = query::from_str("fields[posts]=title&include=author")?;
+    /// query.include_fields::<Post>();
+    ///
+    /// assert!(query.fields.get("posts").unwrap().contains("author"));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`fields`]: #structfield.fields
+    /// [`include`]: #structfield.include
+    pub fn include_fields<T: Resource>(&mut self) {
+        if let Some(schema) = T::schema() {
+            schema.include_fields(&self.include, &mut self.fields);
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for Query {
@@ -224,9 +342,80 @@ impl Serialize for Query {
 }
 
 /// Deserialize a `Query` from the bytes of a percent encoded query string.
+///
+/// If a query parameter can not be deserialized, the returned error references it. A
+/// parameter that is invalid on its own is referenced by its full name, for example
+/// `page[number]`. Otherwise, the error references the member of the query that it
+/// belongs to, for example `page`.
 pub fn from_slice(data: &[u8]) -> Result<Query, Error> {
-    let value = percent_decode(data).decode_utf8()?;
-    Ok(serde_qs::from_bytes(value.as_bytes())?)
+    let value = decode_brackets(str::from_utf8(data)?);
+
+    serde_qs::from_bytes(value.as_bytes()).map_err(|e| match invalid_parameter(&value) {
+        Some(name) => Error::invalid_parameter(name, &e.to_string()),
+        None => e.into(),
+    })
+}
+
+/// Decodes the percent encoded brackets in the parameter names of `data`, so
+/// `page%5Bsize%5D=10` is read as `page[size]=10`.
+///
+/// The values are left as is and decoded by `serde_qs` after the query string is split
+/// into pairs. Decoding the whole query string first would split a value that contains
+/// an encoded `&` or `=`, like an opaque cursor.
+fn decode_brackets(data: &str) -> Cow<'_, str> {
+    if !data.contains('%') {
+        return Cow::Borrowed(data);
+    }
+
+    let pairs = data.split('&').map(|pair| {
+        let (name, value) = match pair.find('=') {
+            Some(index) => pair.split_at(index),
+            None => (pair, ""),
+        };
+
+        let name = name
+            .replace("%5B", "[")
+            .replace("%5b", "[")
+            .replace("%5D", "]")
+            .replace("%5d", "]");
+
+        name + value
+    });
+
+    Cow::Owned(pairs.collect::<Vec<_>>().join("&"))
+}
+
+/// Returns the name of the query parameter in `data` that fails to deserialize.
+///
+/// The parameters are grouped by the member of the query that they belong to. Only the
+/// parameters of a group that fails to deserialize are tried on their own, so each
+/// parameter is deserialized at most twice.
+fn invalid_parameter(data: &str) -> Option<&str> {
+    fn name(pair: &str) -> &str {
+        pair.split('=').next().unwrap_or_default()
+    }
+
+    fn member(pair: &str) -> &str {
+        name(pair).split('[').next().unwrap_or_default()
+    }
+
+    let mut groups = IndexMap::<_, Vec<_>>::new();
+
+    for pair in data.split('&').filter(|pair| !pair.is_empty()) {
+        groups.entry(member(pair)).or_default().push(pair);
+    }
+
+    groups.into_iter().find_map(|(key, pairs)| {
+        if serde_qs::from_str::<Query>(&pairs.join("&")).is_ok() {
+            return None;
+        }
+
+        let single = pairs
+            .into_iter()
+            .find(|pair| serde_qs::from_str::<Query>(pair).is_err());
+
+        Some(single.map_or(key, name))
+    })
 }
 
 /// Deserialize a `Query` from a percent encoded query string.
@@ -234,6 +423,31 @@ pub fn from_str(data: &str) -> Result<Query, Error> {
     from_slice(data.as_bytes())
 }
 
+/// Deserialize a `Query` from the bytes of a percent encoded query string and check its
+/// include paths against `limits`.
+///
+/// The maximum number of included resources is not a part of the query. It is enforced
+/// when a document is rendered with [`Limited`].
+///
+/// [`Limited`]: ../view/struct.Limited.html
+pub fn from_slice_with_limits(data: &[u8], limits: Limits) -> Result<Query, Error> {
+    let query = from_slice(data)?;
+
+    limits.check(&query)?;
+    Ok(query)
+}
+
+/// Deserialize a `Query` from a percent encoded query string and check its include
+/// paths against `limits`.
+///
+/// The maximum number of included resources is not a part of the query. It is enforced
+/// when a document is rendered with [`Limited`].
+///
+/// [`Limited`]: ../view/struct.Limited.html
+pub fn from_str_with_limits(data: &str, limits: Limits) -> Result<Query, Error> {
+    from_slice_with_limits(data.as_bytes(), limits)
+}
+
 /// Serialize the given `Query` as a percent encoded query string.
 pub fn to_string(query: &Query) -> Result<String, Error> {
     let value = serde_qs::to_string(query)?;
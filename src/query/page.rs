@@ -6,21 +6,100 @@ use serde::{
     Deserialize,
 };
 
-/// Limit and offset based pagination parameters.
-#[non_exhaustive]
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct Page {
-    /// The page number. This value is checked to be non-zero when a page is created via
-    /// the constructor method or decoded from a query string. If zero is passed to the
-    /// constructor or decoded from a query string, `1` will be used instead.
-    pub number: u64,
-
-    /// Optionally specifies the maximum number of items to include per page.
-    pub size: Option<u64>,
+/// Pagination parameters.
+///
+/// A page is decoded from the `page` query parameter using one of three strategies,
+/// depending on the members that are present:
+///
+/// * `page[after]`, `page[before]` and `page[size]` are interpreted as a [`Cursor`]
+///   page, as described by the *[cursor pagination]* profile.
+/// * `page[offset]` and `page[limit]` are interpreted as an [`Offset`] page.
+/// * `page[number]` and `page[size]` are interpreted as a [`Number`] page. This is
+///   the default if no members are present.
+/// * `page[size]` on its own is interpreted as a [`Size`] page, since it is accepted
+///   by more than one strategy.
+///
+/// Members of different strategies cannot be combined.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::query::{self, Page};
+///
+/// let query = query::from_str("page[after]=abc&page[size]=10")?;
+///
+/// match query.page {
+///     Some(Page::Cursor { after, size, .. }) => {
+///         assert_eq!(after, Some("abc".to_owned()));
+///         assert_eq!(size, Some(10));
+///     }
+///     _ => panic!("expected a cursor page"),
+/// }
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     example().unwrap();
+/// # }
+/// ```
+///
+/// [`Cursor`]: #variant.Cursor
+/// [`Number`]: #variant.Number
+/// [`Offset`]: #variant.Offset
+/// [`Size`]: #variant.Size
+/// [cursor pagination]: https://jsonapi.org/profiles/ethanresnick/cursor-pagination/
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Page {
+    /// Page number based pagination parameters.
+    Number {
+        /// The page number. This value is checked to be non-zero when a page is created
+        /// via the constructor method or decoded from a query string. If zero is passed
+        /// to the constructor or decoded from a query string, `1` will be used instead.
+        number: u64,
+
+        /// Optionally specifies the maximum number of items to include per page.
+        size: Option<u64>,
+    },
+
+    /// Offset and limit based pagination parameters.
+    Offset {
+        /// The number of items to skip.
+        offset: u64,
+
+        /// Optionally specifies the maximum number of items to include.
+        limit: Option<u64>,
+    },
+
+    /// Cursor based pagination parameters.
+    Cursor {
+        /// Only include items that come after the item with this cursor.
+        after: Option<String>,
+
+        /// Only include items that come before the item with this cursor.
+        before: Option<String>,
+
+        /// Optionally specifies the maximum number of items to include per page.
+        size: Option<u64>,
+    },
+
+    /// Only the maximum number of items to include per page. The page does not choose a
+    /// pagination strategy, so it is interpreted as the first page of the strategy that
+    /// the collection uses.
+    Size {
+        /// The maximum number of items to include per page.
+        size: u64,
+    },
 }
 
 impl Page {
-    /// Returns a new `Page`. If zero is used for `number` it will be treated as `1`.
+    /// Returns a new page number based `Page`. If zero is used for `number` it will be
+    /// treated as `1`.
     ///
     /// # Example
     ///
@@ -35,7 +114,49 @@ impl Page {
     pub fn new(number: u64, size: Option<u64>) -> Self {
         let number = if number > 0 { number } else { 1 };
 
-        Page { number, size }
+        Page::Number { number, size }
+    }
+
+    /// Returns a new offset based `Page`.
+    pub fn offset(offset: u64, limit: Option<u64>) -> Self {
+        Page::Offset { offset, limit }
+    }
+
+    /// Returns a new cursor based `Page`.
+    pub fn cursor(after: Option<String>, before: Option<String>, size: Option<u64>) -> Self {
+        Page::Cursor {
+            after,
+            before,
+            size,
+        }
+    }
+
+    /// Returns a new `Page` that only specifies the maximum number of items to include.
+    pub fn size_only(size: u64) -> Self {
+        Page::Size { size }
+    }
+
+    /// Returns the maximum number of items to include, regardless of the strategy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # fn main() {
+    /// use json_api::query::Page;
+    ///
+    /// assert_eq!(Page::new(2, Some(10)).size(), Some(10));
+    /// assert_eq!(Page::offset(20, Some(5)).size(), Some(5));
+    /// assert_eq!(Page::cursor(None, None, None).size(), None);
+    /// # }
+    /// ```
+    pub fn size(&self) -> Option<u64> {
+        match *self {
+            Page::Number { size, .. } | Page::Cursor { size, .. } => size,
+            Page::Offset { limit, .. } => limit,
+            Page::Size { size } => Some(size),
+        }
     }
 }
 
@@ -50,14 +171,18 @@ impl<'de> Deserialize<'de> for Page {
     where
         D: Deserializer<'de>,
     {
-        use serde::de::{MapAccess, Visitor};
+        use serde::de::{Error, MapAccess, Visitor};
 
-        const FIELDS: &[&str] = &["number", "size"];
+        const FIELDS: &[&str] = &["after", "before", "limit", "number", "offset", "size"];
 
         #[derive(Debug, Deserialize)]
         #[serde(field_identifier, rename_all = "lowercase")]
         enum Field {
+            After,
+            Before,
+            Limit,
             Number,
+            Offset,
             Size,
         }
 
@@ -74,21 +199,52 @@ impl<'de> Deserialize<'de> for Page {
             where
                 A: MapAccess<'de>,
             {
+                let mut after = None;
+                let mut before = None;
+                let mut limit = None;
                 let mut number = None;
+                let mut offset = None;
                 let mut size = None;
 
                 while let Some(key) = access.next_key()? {
                     match key {
+                        Field::After => {
+                            after = access.next_value()?;
+                        }
+                        Field::Before => {
+                            before = access.next_value()?;
+                        }
+                        Field::Limit => {
+                            limit = access.next_value()?;
+                        }
                         Field::Number => {
                             number = access.next_value()?;
                         }
+                        Field::Offset => {
+                            offset = access.next_value()?;
+                        }
                         Field::Size => {
                             size = access.next_value()?;
                         }
                     }
                 }
 
-                Ok(Page::new(number.unwrap_or(1), size))
+                let is_cursor = after.is_some() || before.is_some();
+                let is_offset = offset.is_some() || limit.is_some();
+                let is_number = number.is_some();
+
+                match (is_number, is_offset, is_cursor) {
+                    (false, false, false) => match size {
+                        Some(size) => Ok(Page::size_only(size)),
+                        None => Ok(Page::default()),
+                    },
+                    (true, false, false) => Ok(Page::new(number.unwrap_or(1), size)),
+                    (false, true, false) => Ok(Page::offset(offset.unwrap_or(0), limit)),
+                    (false, false, true) => Ok(Page::cursor(after, before, size)),
+                    _ => Err(Error::custom(
+                        "cannot combine page parameters of different pagination strategies",
+                    )),
+                }
             }
         }
 
@@ -101,16 +257,45 @@ impl Serialize for Page {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Page", 2)?;
-        let number = &self.number;
-        let size = &self.size;
+        let mut state = serializer.serialize_struct("Page", 3)?;
 
-        if *number != 1 {
-            state.serialize_field("number", number)?;
-        }
+        match *self {
+            Page::Number { number, size } => {
+                if number != 1 {
+                    state.serialize_field("number", &number)?;
+                }
+
+                if let Some(ref value) = size {
+                    state.serialize_field("size", value)?;
+                }
+            }
+            Page::Offset { offset, limit } => {
+                state.serialize_field("offset", &offset)?;
 
-        if let Some(ref value) = *size {
-            state.serialize_field("size", value)?;
+                if let Some(ref value) = limit {
+                    state.serialize_field("limit", value)?;
+                }
+            }
+            Page::Cursor {
+                ref after,
+                ref before,
+                size,
+            } => {
+                if let Some(ref value) = *after {
+                    state.serialize_field("after", value)?;
+                }
+
+                if let Some(ref value) = *before {
+                    state.serialize_field("before", value)?;
+                }
+
+                if let Some(ref value) = size {
+                    state.serialize_field("size", value)?;
+                }
+            }
+            Page::Size { ref size } => {
+                state.serialize_field("size", size)?;
+            }
         }
 
         state.end()
@@ -127,21 +312,22 @@ mod tests {
 
         // Page number should always be a positive unsigned integer.
         // If 0 is passed to the constructor, it should be treated as 1.
-        assert_eq!(page.number, 1);
-        assert_eq!(page.size, None);
+        assert_eq!(
+            page,
+            Page::Number {
+                number: 1,
+                size: None
+            }
+        );
 
         for number in 1..5 {
             page = Page::new(number, None);
-
-            assert_eq!(page.number, number);
-            assert_eq!(page.size, None);
+            assert_eq!(page, Page::Number { number, size: None });
         }
 
         for size in (0..10).map(Some) {
             page = Page::new(1, size);
-
-            assert_eq!(page.number, 1);
-            assert_eq!(page.size, size);
+            assert_eq!(page, Page::Number { number: 1, size });
         }
     }
 }
@@ -1,11 +1,15 @@
 use std::mem;
 
 use crate::{
-    doc::{Data, Document, Identifier, Object},
+    doc::{Data, Document, Identifier, Object, Relationship},
     error::Error,
-    query::Query,
-    value::{fields::Key, Set},
-    view::{Context, Render},
+    query::{Limits, Query},
+    schema::Schema,
+    value::{
+        fields::{Key, Path},
+        Set,
+    },
+    view::{linkage, Context, Limited, Render},
 };
 
 /// A trait indicating that the given type can be represented as a resource.
@@ -95,6 +99,119 @@ pub trait Resource {
     ///
     /// [`json_api::to_doc`]: ./fn.to_doc.html
     fn to_object(&self, ctx: &mut Context) -> Result<Object, Error>;
+
+    /// Returns a description of the attributes and relationships of the resource type,
+    /// or `None` if they are unknown.
+    ///
+    /// The [`resource!`] macro implements this method. A relationship that is declared
+    /// with the shorthand `has_one` or `has_many` syntax includes the schema of the
+    /// related resource type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[macro_use]
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// struct Comment(u64);
+    ///
+    /// resource!(Comment, |&self| {
+    ///     kind "comments";
+    ///     id self.0;
+    /// });
+    ///
+    /// struct Post {
+    ///     id: u64,
+    ///     comments: Vec<Comment>,
+    /// }
+    ///
+    /// resource!(Post, |&self| {
+    ///     kind "posts";
+    ///     id self.id;
+    ///
+    ///     has_many comments;
+    /// });
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::{query, Resource};
+    ///
+    /// let schema = Post::schema().unwrap();
+    /// assert!(schema.relationships.contains_key("comments"));
+    ///
+    /// let query = query::from_str("include=comments.author")?;
+    /// assert!(query.validate_include::<Post>().is_err());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`resource!`]: ./macro.resource.html
+    fn schema() -> Option<Schema> {
+        None
+    }
+
+    /// Called when the resource is rendered as primary data and `path` is in the
+    /// `include` query parameter, but does not match a relationship of the rendered
+    /// resources.
+    ///
+    /// Paths that do not match a relationship are ignored by default. Return an error to
+    /// reject the request instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[macro_use]
+    /// # extern crate json_api;
+    /// #
+    /// # fn main() {
+    /// use json_api::doc::Object;
+    /// use json_api::value::Path;
+    /// use json_api::view::Context;
+    /// use json_api::{query, Error, Resource};
+    ///
+    /// struct Post(u64);
+    ///
+    /// impl Resource for Post {
+    ///     # fn kind() -> json_api::value::Key {
+    ///     #     "posts".parse().unwrap()
+    ///     # }
+    ///     #
+    ///     # fn id(&self) -> String {
+    ///     #     self.0.to_string()
+    ///     # }
+    ///     #
+    ///     # fn to_ident(&self, _: &mut Context) -> Result<json_api::doc::Identifier, Error> {
+    ///     #     Ok(json_api::doc::Identifier::new(Self::kind(), self.id()))
+    ///     # }
+    ///     #
+    ///     # fn to_object(&self, _: &mut Context) -> Result<Object, Error> {
+    ///     #     Ok(Object::new(Self::kind(), self.id()))
+    ///     # }
+    ///     #
+    ///     // ...
+    ///
+    ///     fn missing_include(path: &Path) -> Result<(), Error> {
+    ///         let reason = format!(r#"the relationship "{}" does not exist"#, path);
+    ///         Err(Error::invalid_parameter("include", &reason))
+    ///     }
+    /// }
+    ///
+    /// let query = query::from_str("include=author").unwrap();
+    /// let doc = json_api::to_doc::<_, Object>(&Post(1), Some(&query));
+    ///
+    /// assert!(doc.is_err());
+    /// # }
+    /// ```
+    fn missing_include(path: &Path) -> Result<(), Error> {
+        let _ = path;
+        Ok(())
+    }
 }
 
 impl<'a, T: Resource> Render<Identifier> for &'a T {
@@ -119,17 +236,31 @@ impl<'a, T: Resource> Render<Identifier> for &'a [T] {
 }
 
 impl<'a, T: Resource> Render<Object> for &'a T {
+    fn render(self, query: Option<&Query>) -> Result<Document<Object>, Error> {
+        Limited::new(self, Limits::new()).render(query)
+    }
+}
+
+impl<'a, T: Resource> Render<Object> for &'a [T] {
+    fn render(self, query: Option<&Query>) -> Result<Document<Object>, Error> {
+        Limited::new(self, Limits::new()).render(query)
+    }
+}
+
+impl<T: Resource> Render<Object> for Limited<&T> {
     fn render(self, query: Option<&Query>) -> Result<Document<Object>, Error> {
         let mut incl = Set::new();
         let (data, links, meta) = {
-            let mut ctx = Context::new(T::kind(), query, &mut incl);
-            let mut obj = self.to_object(&mut ctx)?;
+            let mut ctx = Context::with_limits(T::kind(), query, &mut incl, self.limits);
+            let mut obj = self.value.to_object(&mut ctx)?;
             let links = mem::take(&mut obj.links);
             let meta = mem::take(&mut obj.meta);
 
             (obj.into(), links, meta)
         };
 
+        finish::<T>(&data, &mut incl, query)?;
+
         Ok(Document::Ok {
             data,
             links,
@@ -140,21 +271,24 @@ impl<'a, T: Resource> Render<Object> for &'a T {
     }
 }
 
-impl<'a, T: Resource> Render<Object> for &'a [T] {
+impl<T: Resource> Render<Object> for Limited<&[T]> {
     fn render(self, query: Option<&Query>) -> Result<Document<Object>, Error> {
         let mut incl = Set::new();
-        let mut data = Vec::with_capacity(self.len());
+        let mut data = Vec::with_capacity(self.value.len());
 
         {
-            let mut ctx = Context::new(T::kind(), query, &mut incl);
+            let mut ctx = Context::with_limits(T::kind(), query, &mut incl, self.limits);
 
-            for item in self {
+            for item in self.value {
                 data.push(item.to_object(&mut ctx)?);
             }
         }
 
+        let data = Data::Collection(data);
+        finish::<T>(&data, &mut incl, query)?;
+
         Ok(Document::Ok {
-            data: Data::Collection(data),
+            data,
             links: Default::default(),
             meta: Default::default(),
             included: incl,
@@ -163,6 +297,84 @@ impl<'a, T: Resource> Render<Object> for &'a [T] {
     }
 }
 
+/// Reports the include paths that do not match a relationship to `T` and removes the
+/// included resources that are no longer linked to `data`.
+fn finish<T: Resource>(
+    data: &Data<Object>,
+    incl: &mut Set<Object>,
+    query: Option<&Query>,
+) -> Result<(), Error> {
+    for path in linkage::missing_includes(data, incl, query) {
+        T::missing_include(&path)?;
+    }
+
+    linkage::prune(data, incl);
+    Ok(())
+}
+
+/// Renders a to-one relationship of a resource. Used by the code generated by
+/// `#[derive(Resource)]`.
+#[doc(hidden)]
+pub fn render_has_one<T>(
+    ctx: &mut Context,
+    key: &Key,
+    value: Option<&T>,
+) -> Result<Relationship, Error>
+where
+    T: Resource,
+{
+    let mut data = None;
+
+    if let Some(item) = value {
+        let mut ctx = ctx.fork(T::kind(), key);
+
+        data = Some(item.to_ident(&mut ctx)?);
+
+        if ctx.included() {
+            let object = item.to_object(&mut ctx)?;
+            ctx.include(object)?;
+        }
+    }
+
+    Ok(Relationship::from(data))
+}
+
+/// Renders a to-many relationship of a resource. Used by the code generated by
+/// `#[derive(Resource)]`.
+#[doc(hidden)]
+pub fn render_has_many<'a, I, T>(
+    ctx: &mut Context,
+    key: &Key,
+    value: I,
+) -> Result<Relationship, Error>
+where
+    I: IntoIterator<Item = &'a T>,
+    T: Resource + 'a,
+{
+    let iter = value.into_iter();
+    let mut ctx = ctx.fork(T::kind(), key);
+    let mut data = match iter.size_hint() {
+        (_, Some(size)) => Vec::with_capacity(size),
+        _ => Vec::new(),
+    };
+
+    if ctx.included() {
+        for item in iter {
+            let object = item.to_object(&mut ctx)?;
+            let ident = Identifier::from(&object);
+
+            ctx.include(object)?;
+            data.push(ident);
+        }
+    } else {
+        for item in iter {
+            data.push(item.to_ident(&mut ctx)?);
+        }
+    }
+
+    Ok(Relationship::from(data))
+}
+
 /// A DSL for implementing the `Resource` trait.
 ///
 /// # Examples
@@ -274,6 +486,12 @@ impl<'a, T: Resource> Render<Object> for &'a [T] {
 ///         }
 ///     }
 ///
+///     // Define a relationship that only contains links. The related data is never
+///     // loaded, which is useful for large to-many relationships.
+///     has_many "likes", {
+///         link "related", format!("/articles/{}/likes", self.id);
+///     }
+///
 ///     // You can also define links with granular details as well
 ///     link "self", {
 ///         href format!("/articles/{}", self.id);
@@ -320,6 +538,21 @@ macro_rules! resource {
                 extract_resource_id!({ $($rest)* }).to_string()
             }
 
+            fn schema() -> Option<$crate::Schema> {
+                let kind = <$target as $crate::Resource>::kind();
+                let mut schema = $crate::Schema::new(kind);
+
+                {
+                    let _attrs = &mut schema.attributes;
+                    let _related = &mut schema.relationships;
+                    expand_resource_impl!(@schema _attrs, _related, {
+                        $($rest)*
+                    });
+                }
+
+                Some(schema)
+            }
+
             fn to_ident(
                 &$this,
                 _: &mut $crate::view::Context,
@@ -407,8 +640,9 @@ macro_rules! expand_resource_impl {
         attr $key:expr, $value:block
         $($rest:tt)*
     }) => {
-        if $ctx.field($key) {
-            let key = $key.parse::<$crate::value::Key>()?;
+        let key = $key.parse::<$crate::value::Key>()?;
+
+        if $ctx.field(&key) {
             let value = $crate::to_value($value)?;
 
             $attrs.insert(key, value);
@@ -437,8 +671,9 @@ macro_rules! expand_resource_impl {
         has_many $key:expr, { $($body:tt)* }
         $($rest:tt)*
     }) => {
-        if $ctx.field($key) {
-            let key = $key.parse::<$crate::value::Key>()?;
+        let key = $key.parse::<$crate::value::Key>()?;
+
+        if $ctx.field(&key) {
             expand_resource_impl!(@has_many $this, $related, key, $ctx, {
                 $($body)*
             });
@@ -453,8 +688,9 @@ macro_rules! expand_resource_impl {
         has_one $key:expr, { $($body:tt)* }
         $($rest:tt)*
     }) => {
-        if $ctx.field($key) {
-            let key = $key.parse::<$crate::value::Key>()?;
+        let key = $key.parse::<$crate::value::Key>()?;
+
+        if $ctx.field(&key) {
             expand_resource_impl!(@has_one $this, $related, key, $ctx, {
                 $($body)*
             });
@@ -485,6 +721,98 @@ macro_rules! expand_resource_impl {
         });
     };
 
+    (@schema $attrs:ident, $related:ident, {
+        attr $key:expr, $value:block
+        $($rest:tt)*
+    }) => {
+        $attrs.insert($key.parse::<$crate::value::Key>().ok()?);
+
+        expand_resource_impl!(@schema $attrs, $related, {
+            $($rest)*
+        });
+    };
+
+    (@schema $attrs:ident, $related:ident, {
+        attr $field:ident;
+        $($rest:tt)*
+    }) => {
+        $attrs.insert(stringify!($field).parse::<$crate::value::Key>().ok()?);
+
+        expand_resource_impl!(@schema $attrs, $related, {
+            $($rest)*
+        });
+    };
+
+    (@schema $attrs:ident, $related:ident, {
+        attrs $($field:ident),+;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@schema $attrs, $related, {
+            $(attr $field;)+
+            $($rest)*
+        });
+    };
+
+    (@schema $attrs:ident, $related:ident, {
+        has_many $key:expr, { $($body:tt)* }
+        $($rest:tt)*
+    }) => {
+        $related.insert(
+            $key.parse::<$crate::value::Key>().ok()?,
+            $crate::__private::unknown_schema,
+        );
+
+        expand_resource_impl!(@schema $attrs, $related, {
+            $($rest)*
+        });
+    };
+
+    (@schema $attrs:ident, $related:ident, {
+        has_one $key:expr, { $($body:tt)* }
+        $($rest:tt)*
+    }) => {
+        $related.insert(
+            $key.parse::<$crate::value::Key>().ok()?,
+            $crate::__private::unknown_schema,
+        );
+
+        expand_resource_impl!(@schema $attrs, $related, {
+            $($rest)*
+        });
+    };
+
+    (@schema $attrs:ident, $related:ident, {
+        has_many $($field:ident),*;
+        $($rest:tt)*
+    }) => {
+        $(
+            $related.insert(
+                stringify!($field).parse::<$crate::value::Key>().ok()?,
+                $crate::__private::related_schema::<Self, _, _, _>(|this| this.$field.iter()),
+            );
+        )*
+
+        expand_resource_impl!(@schema $attrs, $related, {
+            $($rest)*
+        });
+    };
+
+    (@schema $attrs:ident, $related:ident, {
+        has_one $($field:ident),*;
+        $($rest:tt)*
+    }) => {
+        $(
+            $related.insert(
+                stringify!($field).parse::<$crate::value::Key>().ok()?,
+                $crate::__private::related_schema::<Self, _, _, _>(|this| this.$field.as_ref()),
+            );
+        )*
+
+        expand_resource_impl!(@schema $attrs, $related, {
+            $($rest)*
+        });
+    };
+
     (@has_many $this:ident, $related:ident, $key:ident, $ctx:ident, {
         data $value:block
         $($rest:tt)*
@@ -501,7 +829,7 @@ macro_rules! expand_resource_impl {
                     let object = $crate::Resource::to_object(item, &mut ctx)?;
                     let ident = $crate::doc::Identifier::from(&object);
 
-                    ctx.include(object);
+                    ctx.include(object)?;
                     data.push(ident);
                 }
             } else {
@@ -544,7 +872,7 @@ macro_rules! expand_resource_impl {
 
                 if ctx.included() {
                     let object = $crate::Resource::to_object(item, &mut ctx)?;
-                    ctx.include(object);
+                    ctx.include(object)?;
                 }
             }
 
@@ -568,6 +896,65 @@ macro_rules! expand_resource_impl {
         $related.insert($key, rel);
     };
 
+    (@has_many $this:ident, $related:ident, $key:ident, $ctx:ident, {
+        data $value:expr;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@has_many $this, $related, $key, $ctx, {
+            data { $value }
+            $($rest)*
+        });
+    };
+
+    (@has_one $this:ident, $related:ident, $key:ident, $ctx:ident, {
+        data $value:expr;
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@has_one $this, $related, $key, $ctx, {
+            data { $value }
+            $($rest)*
+        });
+    };
+
+    // A relationship without data only contains links and meta members.
+    (@has_many $this:ident, $related:ident, $key:ident, $ctx:ident, {
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@without_data $this, $related, $key, {
+            $($rest)*
+        });
+    };
+
+    (@has_one $this:ident, $related:ident, $key:ident, $ctx:ident, {
+        $($rest:tt)*
+    }) => {
+        expand_resource_impl!(@without_data $this, $related, $key, {
+            $($rest)*
+        });
+    };
+
+    (@without_data $this:ident, $related:ident, $key:ident, {
+        $($rest:tt)*
+    }) => {
+        let mut rel = $crate::doc::Relationship::default();
+
+        {
+            let _links = &mut rel.links;
+            expand_resource_impl!(@links $this, _links, {
+                $($rest)*
+            });
+        }
+
+        {
+            let _meta = &mut rel.meta;
+            expand_resource_impl!(@meta $this, _meta, {
+                $($rest)*
+            });
+        }
+
+        $related.insert($key, rel);
+    };
+
     (@links $this:ident, $links:ident, {
         link $key:expr, { $($body:tt)* }
         $($rest:tt)*
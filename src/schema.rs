@@ -0,0 +1,231 @@
+use crate::{
+    error::Error,
+    resource::Resource,
+    value::{Key, Map, Path, Set},
+};
+
+/// Describes the attributes and relationships of a resource type.
+///
+/// A schema is returned by [`Resource::schema`]. It is used to reject the paths of the
+/// `include` query parameter and the members of sparse fieldsets that do not match a
+/// field before a document is rendered.
+///
+/// [`Resource::schema`]: ./trait.Resource.html#method.schema
+#[derive(Clone, Debug)]
+pub struct Schema {
+    /// The names of the attributes of the resource, as they appear in a rendered
+    /// document.
+    pub attributes: Set<Key>,
+
+    /// The type of the resource.
+    pub kind: Key,
+
+    /// The names of the relationships of the resource, as they appear in a rendered
+    /// document. Each value returns the schema of the related resource type, or `None`
+    /// if it is unknown.
+    pub relationships: Map<Key, fn() -> Option<Schema>>,
+}
+
+impl Schema {
+    /// Returns a new `Schema` for the resource type `kind` without attributes or
+    /// relationships.
+    pub fn new(kind: Key) -> Self {
+        Schema {
+            kind,
+            attributes: Set::new(),
+            relationships: Map::new(),
+        }
+    }
+
+    /// Returns `true` if `name` is an attribute or relationship of the resource.
+    pub fn has_field(&self, name: &str) -> bool {
+        self.attributes.contains(name) || self.relationships.contains_key(name)
+    }
+
+    /// Returns an error if a member of a sparse fieldset in `fields` is not a field of
+    /// the resource type that it applies to.
+    ///
+    /// Fieldsets are checked against `self` and the known schemas of the resource types
+    /// that are related to `self`. Fieldsets for other resource types are accepted.
+    ///
+    /// The returned error references the query parameter of the fieldset, for example
+    /// `fields[posts]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::{query, Schema};
+    ///
+    /// let mut schema = Schema::new("posts".parse()?);
+    /// schema.attributes.insert("title".parse()?);
+    ///
+    /// let valid = query::from_str("fields[posts]=title")?;
+    /// let invalid = query::from_str("fields[posts]=titel")?;
+    ///
+    /// assert!(schema.validate_fields(&valid.fields).is_ok());
+    /// assert!(schema.validate_fields(&invalid.fields).is_err());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn validate_fields(&self, fields: &Map<Key, Set>) -> Result<(), Error> {
+        let schemas = self.related();
+
+        for (kind, names) in fields {
+            let schema = match schemas.get(kind) {
+                Some(schema) => schema,
+                None => continue,
+            };
+
+            if let Some(name) = names.iter().find(|name| !schema.has_field(name)) {
+                let param = format!("fields[{}]", kind);
+                let reason = format!(r#""{}" is not a field of "{}""#, name, kind);
+
+                return Err(Error::invalid_parameter(&param, &reason));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the relationships that are traversed by the paths in `include` to the
+    /// sparse fieldsets in `fields`.
+    ///
+    /// The specification allows a sparse fieldset to exclude the relationships that
+    /// link included resources to the primary data. Call this method to keep the
+    /// included resources fully linked instead. Fieldsets that are not present in
+    /// `fields` already include every field and are not added.
+    pub fn include_fields(&self, include: &Set<Path>, fields: &mut Map<Key, Set>) {
+        for path in include {
+            let mut current = Some(self.clone());
+
+            for key in path.iter() {
+                let schema = match current.take() {
+                    Some(schema) => schema,
+                    None => break,
+                };
+
+                if let Some(names) = fields.iter_mut().find(|(kind, _)| **kind == schema.kind) {
+                    names.1.insert(key.clone());
+                }
+
+                current = schema.relationships.get(key).and_then(|related| related());
+            }
+        }
+    }
+
+    /// Returns the known schemas of `self` and the resource types that are related to
+    /// `self`, keyed by resource type.
+    fn related(&self) -> Map<Key, Schema> {
+        let mut schemas = Map::new();
+        let mut queue = vec![self.clone()];
+
+        while let Some(schema) = queue.pop() {
+            if schemas.contains_key(&schema.kind) {
+                continue;
+            }
+
+            queue.extend(
+                schema
+                    .relationships
+                    .values()
+                    .filter_map(|related| related()),
+            );
+            schemas.insert(schema.kind.clone(), schema);
+        }
+
+        schemas
+    }
+
+    /// Returns an error if a path in `include` does not match a relationship.
+    ///
+    /// Each segment of a path is checked against the schema of the resource type that
+    /// is related by the previous segment. If the schema of a related resource type is
+    /// unknown, the remaining segments of the path are accepted.
+    ///
+    /// The returned error references the `include` query parameter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate json_api;
+    /// #
+    /// # use json_api::Error;
+    /// #
+    /// # fn example() -> Result<(), Error> {
+    /// use json_api::Schema;
+    ///
+    /// let mut schema = Schema::new("posts".parse()?);
+    /// schema.relationships.insert("author".parse()?, || None);
+    ///
+    /// assert!(schema.validate_include(&"author".parse()?).is_ok());
+    /// assert!(schema.validate_include(&"author.avatar".parse()?).is_ok());
+    /// assert!(schema.validate_include(&"comments".parse()?).is_err());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// # example().unwrap();
+    /// # }
+    /// ```
+    pub fn validate_include(&self, include: &Set<Path>) -> Result<(), Error> {
+        for path in include {
+            self.validate_path(path, path)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_path(&self, path: &Path, keys: &[Key]) -> Result<(), Error> {
+        let (key, rest) = match keys.split_first() {
+            Some(item) => item,
+            None => return Ok(()),
+        };
+
+        match self.relationships.get(key) {
+            Some(_) if rest.is_empty() => Ok(()),
+            Some(related) => match related() {
+                Some(schema) => schema.validate_path(path, rest),
+                None => Ok(()),
+            },
+            None => {
+                let reason = format!(
+                    r#""{}" is not a relationship of "{}" in "{}""#,
+                    key, self.kind, path
+                );
+
+                Err(Error::invalid_parameter("include", &reason))
+            }
+        }
+    }
+}
+
+/// Returns the schema of the resource type that is yielded by the iterator returned
+/// from `f`. Used by `resource!` and `#[derive(Resource)]`.
+#[doc(hidden)]
+pub fn related_schema<'a, S, F, I, T>(_: F) -> fn() -> Option<Schema>
+where
+    S: 'a,
+    F: FnOnce(&'a S) -> I,
+    I: IntoIterator<Item = &'a T>,
+    T: Resource + 'a,
+{
+    T::schema
+}
+
+/// The schema of a relationship whose related resource type is unknown. Used by
+/// `resource!`.
+#[doc(hidden)]
+pub fn unknown_schema() -> Option<Schema> {
+    None
+}
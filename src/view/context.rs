@@ -1,6 +1,7 @@
 use crate::{
     doc::Object,
-    query::Query,
+    error::Error,
+    query::{Limits, Query},
     value::{
         fields::{Key, Path, Segment},
         Set,
@@ -26,6 +27,7 @@ use crate::{
 pub struct Context<'v> {
     incl: &'v mut Set<Object>,
     kind: Key,
+    limits: Limits,
     path: Path,
     query: Option<&'v Query>,
 }
@@ -58,8 +60,23 @@ impl<'v> Context<'v> {
     /// # }
     /// ```
     pub fn new(kind: Key, query: Option<&'v Query>, included: &'v mut Set<Object>) -> Self {
+        Context::with_limits(kind, query, included, Limits::new())
+    }
+
+    /// Creates a new, root context that enforces the maximum number of included
+    /// resources of `limits`.
+    ///
+    /// Child contexts that are created with the `fork` method share the `limits` of
+    /// their parent.
+    pub fn with_limits(
+        kind: Key,
+        query: Option<&'v Query>,
+        included: &'v mut Set<Object>,
+        limits: Limits,
+    ) -> Self {
         Context {
             kind,
+            limits,
             query,
             incl: included,
             path: Path::new(),
@@ -79,6 +96,7 @@ impl<'v> Context<'v> {
         Context {
             kind,
             incl: self.incl,
+            limits: self.limits,
             path: self.path.join(key),
             query: self.query,
         }
@@ -89,8 +107,15 @@ impl<'v> Context<'v> {
     /// If the set did not have this value present, `true` is returned.
     ///
     /// If the set did have this value present, `false` is returned.
-    pub fn include(&mut self, value: Object) -> bool {
-        self.incl.insert(value)
+    ///
+    /// Returns an error if adding `value` would exceed the maximum number of included
+    /// resources in the limits of the context.
+    pub fn include(&mut self, value: Object) -> Result<bool, Error> {
+        if !self.incl.contains(&value) {
+            self.limits.check_included(self.incl.len())?;
+        }
+
+        Ok(self.incl.insert(value))
     }
 
     /// Returns `true` if the context is valid with respect to parent context(s).
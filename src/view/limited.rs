@@ -0,0 +1,67 @@
+use crate::query::Limits;
+
+/// Wraps a value to render it with limits on the compound document.
+///
+/// Rendering fails with an include limit error if the `included` member of the
+/// document would exceed the maximum number of included resources of `limits`. The
+/// depth and number of include paths are checked when a query is parsed with
+/// [`from_str_with_limits`].
+///
+/// # Example
+///
+/// ```
+/// #[macro_use]
+/// extern crate json_api;
+/// #
+/// # use json_api::Error;
+///
+/// use json_api::doc::Object;
+/// use json_api::query::{self, Limits};
+/// use json_api::view::Limited;
+///
+/// struct Person {
+///     id: u64,
+///     friends: Vec<Person>,
+/// }
+///
+/// resource!(Person, |&self| {
+///     kind "people";
+///     id self.id;
+///
+///     has_many friends;
+/// });
+///
+/// # fn example() -> Result<(), Error> {
+/// let limits = Limits {
+///     max_included: Some(1),
+///     ..Default::default()
+/// };
+///
+/// let friends = vec![Person { id: 2, friends: vec![] }, Person { id: 3, friends: vec![] }];
+/// let person = Person { id: 1, friends };
+/// let query = query::from_str_with_limits("include=friends", limits)?;
+///
+/// assert!(json_api::to_doc::<_, Object>(&person, Some(&query)).is_ok());
+/// assert!(json_api::to_doc::<_, Object>(Limited::new(&person, limits), Some(&query)).is_err());
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`from_str_with_limits`]: ../query/fn.from_str_with_limits.html
+#[derive(Clone, Copy, Debug)]
+pub struct Limited<T> {
+    pub(crate) limits: Limits,
+    pub(crate) value: T,
+}
+
+impl<T> Limited<T> {
+    /// Returns a new `Limited` that renders `value` with `limits`.
+    pub fn new(value: T, limits: Limits) -> Self {
+        Limited { limits, value }
+    }
+}
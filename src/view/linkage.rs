@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use crate::{
+    doc::{validate, Data, Object},
+    query::Query,
+    value::{Path, Set},
+};
+
+/// Removes the included resources that are not reachable from `data`, as well as the
+/// included resources that are already a part of `data`.
+///
+/// A resource can be included and later lose the relationship that references it
+/// because of a sparse fieldset. The remaining set of included resources is both fully
+/// linked and minimal.
+pub(crate) fn prune(data: &Data<Object>, included: &mut Set<Object>) {
+    let unlinked = validate::unlinked(data, included);
+    let primary = match *data {
+        Data::Member(ref data) => data.iter().collect(),
+        Data::Collection(ref data) => data.iter().collect::<Vec<_>>(),
+    };
+
+    if unlinked.is_empty() && primary.iter().all(|item| !included.contains(*item)) {
+        return;
+    }
+
+    *included = included
+        .drain(..)
+        .enumerate()
+        .filter(|&(index, ref item)| !unlinked.contains(&index) && !primary.contains(&item))
+        .map(|(_, item)| item)
+        .collect();
+}
+
+/// Returns the include paths of `query` that do not match a relationship of the
+/// resources that were rendered.
+///
+/// A path is only considered missing if there was at least one resource at the level
+/// of the path that could have had the relationship. If every value of a relationship
+/// at an intermediate level is empty, the remaining segments can not be checked.
+/// Resources whose sparse fieldset excludes the relationship are not taken into
+/// account, since the relationship was removed from them on purpose.
+pub(crate) fn missing_includes(
+    data: &Data<Object>,
+    included: &Set<Object>,
+    query: Option<&Query>,
+) -> Vec<Path> {
+    let query = match query {
+        Some(query) => query,
+        None => return Vec::new(),
+    };
+
+    let index = included
+        .iter()
+        .map(|item| ((&*item.kind, &*item.id), item))
+        .collect::<HashMap<_, _>>();
+
+    let root = match *data {
+        Data::Member(ref data) => data.iter().collect(),
+        Data::Collection(ref data) => data.iter().collect::<Vec<_>>(),
+    };
+
+    let mut missing = Vec::new();
+
+    for path in &query.include {
+        let mut level = root.clone();
+
+        for key in path.iter() {
+            level.retain(|item| {
+                query
+                    .fields
+                    .get(&item.kind)
+                    .is_none_or(|fields| fields.contains(&**key))
+            });
+
+            if level.is_empty() {
+                break;
+            }
+
+            let related = level
+                .iter()
+                .filter_map(|item| item.relationships.get(key))
+                .collect::<Vec<_>>();
+
+            if related.is_empty() {
+                missing.push(path.clone());
+                break;
+            }
+
+            level = related
+                .into_iter()
+                .flat_map(|rel| match rel.data {
+                    Some(Data::Member(ref data)) => data.iter().collect(),
+                    Some(Data::Collection(ref data)) => data.iter().collect(),
+                    None => Vec::new(),
+                })
+                .filter_map(|ident| {
+                    let id = ident.id.as_deref()?;
+                    index.get(&(&*ident.kind, id)).cloned()
+                })
+                .collect();
+        }
+    }
+
+    missing
+}
@@ -9,7 +9,14 @@
 //! [functions exported from the crate root]: ../index.html#functions
 
 mod context;
+mod limited;
+pub(crate) mod linkage;
+mod pagination;
+mod relationship;
 mod render;
 
 pub use self::context::Context;
+pub use self::limited::Limited;
+pub use self::pagination::{pagination_links, Paginated, Pagination};
+pub use self::relationship::{relationship_links, Linkage};
 pub use self::render::Render;
@@ -0,0 +1,214 @@
+use crate::{
+    doc::{Document, Link, PrimaryData},
+    error::Error,
+    query::{self, Page, Query},
+    value::{Key, Map},
+    view::Render,
+};
+
+/// Describes the collection that is being paginated.
+///
+/// Page number and offset based pages require the total number of items in the
+/// collection. Cursor based pages require the cursors of the first and last items of
+/// the page, since the total is often unknown.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Pagination {
+    /// The total number of items in the collection.
+    Total(u64),
+
+    /// The cursors of the first and last items of the current page.
+    Cursor {
+        /// The cursor of the first item of the current page.
+        first: Option<String>,
+
+        /// The cursor of the last item of the current page.
+        last: Option<String>,
+
+        /// Whether there are items after the current page.
+        has_next: bool,
+
+        /// Whether there are items before the current page.
+        has_prev: bool,
+    },
+}
+
+/// Wraps a value to render it with pagination links.
+///
+/// The `first`, `prev`, `next`, and `last` links are added to the top-level links of
+/// the rendered document. Each link uses `base` as its path and re-encodes the query
+/// that is passed to [`render`] with a different page.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::Object;
+/// use json_api::query;
+/// use json_api::view::{Paginated, Pagination};
+///
+/// let query = query::from_str("page[number]=2&page[size]=10")?;
+/// let value = Paginated::new(Vec::<Object>::new(), "/posts", Pagination::Total(35));
+/// let doc = json_api::to_doc::<_, Object>(value, Some(&query))?;
+/// let href = |name| doc.links().get(name).map(|link| link.to_string());
+///
+/// assert_eq!(href("first"), Some("/posts?page[size]=10".to_owned()));
+/// assert_eq!(href("prev"), Some("/posts?page[size]=10".to_owned()));
+/// assert_eq!(href("next"), Some("/posts?page[number]=3&page[size]=10".to_owned()));
+/// assert_eq!(href("last"), Some("/posts?page[number]=4&page[size]=10".to_owned()));
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`render`]: ./trait.Render.html#tymethod.render
+#[derive(Clone, Debug)]
+pub struct Paginated<T> {
+    base: String,
+    pagination: Pagination,
+    value: T,
+}
+
+impl<T> Paginated<T> {
+    /// Returns a new `Paginated` that renders `value` with pagination links to `base`.
+    pub fn new<B: Into<String>>(value: T, base: B, pagination: Pagination) -> Self {
+        Paginated {
+            pagination,
+            value,
+            base: base.into(),
+        }
+    }
+}
+
+impl<D, T> Render<D> for Paginated<T>
+where
+    D: PrimaryData,
+    T: Render<D>,
+{
+    fn render(self, query: Option<&Query>) -> Result<Document<D>, Error> {
+        let mut doc = self.value.render(query)?;
+        let default = Query::new();
+        let links = pagination_links(&self.base, query.unwrap_or(&default), &self.pagination)?;
+
+        doc.links_mut().extend(links);
+
+        Ok(doc)
+    }
+}
+
+/// Returns the `first`, `prev`, `next`, and `last` links for the page of `query`.
+///
+/// Links that do not apply to the current page are omitted. Cursor based pages never
+/// have a `last` link. A query without a page or with only `page[size]` is the first
+/// page of either strategy. Returns an invalid parameter error if `pagination` does not
+/// match the pagination strategy of `query`.
+pub fn pagination_links(
+    base: &str,
+    query: &Query,
+    pagination: &Pagination,
+) -> Result<Map<Key, Link>, Error> {
+    let mut pages = Vec::with_capacity(4);
+    // A query without a page, or with only a page size, requests the first page of
+    // whichever strategy the collection uses.
+    let page = match (query.page.clone(), pagination) {
+        (None, &Pagination::Total(_)) => Page::default(),
+        (None, &Pagination::Cursor { .. }) => Page::cursor(None, None, None),
+        (Some(Page::Size { size }), &Pagination::Total(_)) => Page::new(1, Some(size)),
+        (Some(Page::Size { size }), &Pagination::Cursor { .. }) => {
+            Page::cursor(None, None, Some(size))
+        }
+        (Some(page), _) => page,
+    };
+
+    match (page, pagination) {
+        (Page::Number { number, size }, &Pagination::Total(total)) => {
+            let last = match size {
+                Some(size) if size > 0 => total.div_ceil(size).max(1),
+                _ => 1,
+            };
+
+            pages.push(("first", Page::new(1, size)));
+
+            if number > 1 {
+                pages.push(("prev", Page::new((number - 1).min(last), size)));
+            }
+
+            if number < last {
+                pages.push(("next", Page::new(number + 1, size)));
+            }
+
+            pages.push(("last", Page::new(last, size)));
+        }
+        (Page::Offset { offset, limit }, &Pagination::Total(total)) => {
+            let last = match limit {
+                Some(limit) if limit > 0 && total > 0 => (total - 1) / limit * limit,
+                _ => 0,
+            };
+
+            pages.push(("first", Page::offset(0, limit)));
+
+            if let Some(limit) = limit.filter(|_| offset > 0) {
+                pages.push((
+                    "prev",
+                    Page::offset(offset.saturating_sub(limit), limit.into()),
+                ));
+            }
+
+            if let Some(limit) = limit.filter(|limit| offset + limit < total) {
+                pages.push(("next", Page::offset(offset + limit, limit.into())));
+            }
+
+            pages.push(("last", Page::offset(last, limit)));
+        }
+        (
+            Page::Cursor { size, .. },
+            &Pagination::Cursor {
+                ref first,
+                ref last,
+                has_next,
+                has_prev,
+            },
+        ) => {
+            pages.push(("first", Page::cursor(None, None, size)));
+
+            if let Some(cursor) = first.as_ref().filter(|_| has_prev) {
+                pages.push(("prev", Page::cursor(None, Some(cursor.clone()), size)));
+            }
+
+            if let Some(cursor) = last.as_ref().filter(|_| has_next) {
+                pages.push(("next", Page::cursor(Some(cursor.clone()), None, size)));
+            }
+        }
+        _ => {
+            return Err(Error::invalid_parameter(
+                "page",
+                "the pagination strategy is not supported by this collection",
+            ));
+        }
+    }
+
+    let mut query = query.clone();
+    let mut links = Map::with_capacity(pages.len());
+
+    for (name, page) in pages {
+        query.page = Some(page);
+
+        let params = query::to_string(&query)?;
+        let link = if params.is_empty() {
+            base.parse()?
+        } else {
+            format!("{}?{}", base, params).parse()?
+        };
+
+        links.insert(Key::from_raw(name.to_owned()), link);
+    }
+
+    Ok(links)
+}
@@ -0,0 +1,101 @@
+use http::Uri;
+
+use crate::{
+    doc::{Document, Link, PrimaryData},
+    error::Error,
+    query::Query,
+    value::{Key, Map},
+    view::Render,
+};
+
+/// Wraps the linkage of a relationship to render it with the links of a relationship
+/// endpoint, for example in response to `GET /posts/1/relationships/comments`.
+///
+/// The `self` and `related` links that are returned by [`relationship_links`] for
+/// `path` are added to the top-level links of the rendered document.
+///
+/// # Example
+///
+/// ```
+/// # extern crate json_api;
+/// #
+/// # use json_api::Error;
+/// #
+/// # fn example() -> Result<(), Error> {
+/// use json_api::doc::Identifier;
+/// use json_api::view::Linkage;
+///
+/// let value = Linkage::new(Vec::<Identifier>::new(), "/posts/1/relationships/comments");
+/// let doc = json_api::to_doc::<_, Identifier>(value, None)?;
+/// let href = |name| doc.links().get(name).map(|link| link.to_string());
+///
+/// assert_eq!(href("self"), Some("/posts/1/relationships/comments".to_owned()));
+/// assert_eq!(href("related"), Some("/posts/1/comments".to_owned()));
+/// #
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// # example().unwrap();
+/// # }
+/// ```
+///
+/// [`relationship_links`]: ./fn.relationship_links.html
+#[derive(Clone, Debug)]
+pub struct Linkage<T> {
+    path: String,
+    value: T,
+}
+
+impl<T> Linkage<T> {
+    /// Returns a new `Linkage` that renders `value` with the links of the relationship
+    /// endpoint at `path`.
+    pub fn new<P: Into<String>>(value: T, path: P) -> Self {
+        Linkage {
+            value,
+            path: path.into(),
+        }
+    }
+}
+
+impl<D, T> Render<D> for Linkage<T>
+where
+    D: PrimaryData,
+    T: Render<D>,
+{
+    fn render(self, query: Option<&Query>) -> Result<Document<D>, Error> {
+        let mut doc = self.value.render(query)?;
+
+        doc.links_mut().extend(relationship_links(&self.path)?);
+        Ok(doc)
+    }
+}
+
+/// Returns the `self` and `related` links of the relationship endpoint at `path`.
+///
+/// The `self` link is `path`. The `related` link is the same URI without its last
+/// `relationships` path segment, so `/posts/1/relationships/comments` is related to
+/// `/posts/1/comments`. The scheme and authority of an absolute URI are kept. If `path`
+/// does not have a `relationships` segment, only the `self` link is returned.
+pub fn relationship_links(path: &str) -> Result<Map<Key, Link>, Error> {
+    let uri = path.parse::<Uri>()?;
+    let mut links = Map::new();
+    let mut segments = uri.path().split('/').collect::<Vec<_>>();
+
+    links.insert("self".parse()?, path.parse()?);
+
+    if let Some(index) = segments.iter().rposition(|seg| *seg == "relationships") {
+        segments.remove(index);
+
+        let related = match (uri.scheme(), uri.authority()) {
+            (Some(scheme), Some(authority)) => {
+                format!("{}://{}{}", scheme, authority, segments.join("/"))
+            }
+            _ => segments.join("/"),
+        };
+
+        links.insert("related".parse()?, related.parse()?);
+    }
+
+    Ok(links)
+}
@@ -0,0 +1,170 @@
+use json_api::{
+    doc::{
+        atomic::{self, AtomicDocument, OpCode, Operation, OperationData, OperationResult},
+        Data, Object, Version,
+    },
+    http::StatusCode,
+    Error,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Article {
+    id: String,
+    title: String,
+}
+
+fn operations(value: serde_json::Value) -> Result<Vec<Operation>, Error> {
+    match atomic::from_str(&value.to_string())? {
+        AtomicDocument::Operations { operations, .. } => Ok(operations),
+        _ => Err(Error::from("expected an atomic:operations document")),
+    }
+}
+
+#[test]
+fn operation_data_from_target() -> Result<(), Error> {
+    let ops = operations(json!({
+        "atomic:operations": [{
+            "op": "update",
+            "data": {
+                "type": "articles",
+                "id": "13",
+                "attributes": { "title": "To TDD or Not" },
+            },
+        }, {
+            "op": "update",
+            "ref": { "type": "articles", "id": "13", "relationship": "author" },
+            "data": null,
+        }, {
+            "op": "add",
+            "ref": { "type": "articles", "id": "1", "relationship": "comments" },
+            "data": [{ "type": "comments", "id": "123" }],
+        }, {
+            "op": "remove",
+            "href": "/articles/1/relationships/comments",
+            "data": [{ "type": "comments", "id": "12" }],
+        }],
+    }))?;
+
+    assert_eq!(ops.len(), 4);
+    assert!(matches!(ops[0].data, Some(OperationData::Object(_))));
+    assert!(!ops[0].is_relationship());
+
+    match ops[1].data {
+        Some(OperationData::Linkage(Data::Member(ref data))) => assert!(data.is_none()),
+        ref data => panic!("unexpected data: {:?}", data),
+    }
+
+    assert_eq!(ops[2].op, OpCode::Add);
+    assert!(ops[2].is_relationship());
+    assert!(matches!(
+        ops[2].relationship().and_then(|rel| rel.data),
+        Some(Data::Collection(ref data)) if data.len() == 1
+    ));
+
+    assert_eq!(ops[3].op, OpCode::Remove);
+    assert!(ops[3].is_relationship());
+
+    Ok(())
+}
+
+#[test]
+fn operation_with_ref_and_href() {
+    let result = operations(json!({
+        "atomic:operations": [{
+            "op": "remove",
+            "ref": { "type": "articles", "id": "13" },
+            "href": "/articles/13",
+        }],
+    }));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn invalid_operation_pointer() -> Result<(), Error> {
+    let e = operations(json!({
+        "atomic:operations": [{
+            "op": "remove",
+            "ref": { "type": "articles", "id": "13" },
+        }, {
+            "op": "remove",
+            "ref": { "type": "articles", "id": "14" },
+            "data": { "type": "articles", "id": "14" },
+        }],
+    }))
+    .unwrap_err();
+
+    assert_eq!(e.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        e.error_source().and_then(|source| source.pointer),
+        Some("/atomic:operations/1/data".to_owned())
+    );
+
+    let e = operations(json!({
+        "atomic:operations": [{ "ref": { "type": "articles", "id": "13" } }],
+    }))
+    .unwrap_err();
+
+    assert_eq!(
+        e.error_source().and_then(|source| source.pointer),
+        Some("/atomic:operations/0".to_owned())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn from_operation() -> Result<(), Error> {
+    let mut ops = operations(json!({
+        "atomic:operations": [{
+            "op": "update",
+            "data": {
+                "type": "articles",
+                "id": "13",
+                "attributes": { "title": "To TDD or Not" },
+            },
+        }],
+    }))?;
+
+    let article: Article = atomic::from_operation(ops.remove(0))?;
+
+    assert_eq!(
+        article,
+        Article {
+            id: "13".to_owned(),
+            title: "To TDD or Not".to_owned(),
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn results_round_trip() -> Result<(), Error> {
+    let article = Object::new("articles".parse()?, "13".to_owned());
+    let doc = AtomicDocument::from_results(vec![article.into(), OperationResult::default()]);
+    let value = serde_json::to_value(&doc)?;
+
+    assert_eq!(
+        value,
+        json!({
+            "atomic:results": [{ "data": { "type": "articles", "id": "13" } }, {}],
+            "jsonapi": { "ext": [atomic::EXT_URI], "version": "1.1" },
+        })
+    );
+
+    let doc: AtomicDocument = serde_json::from_value(value)?;
+    let results = doc.results().unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].data.as_ref().map(|obj| &*obj.id), Some("13"));
+
+    match doc {
+        AtomicDocument::Results { jsonapi, .. } => assert_eq!(jsonapi.version, Version::V1_1),
+        _ => panic!("expected an atomic:results document"),
+    }
+
+    Ok(())
+}
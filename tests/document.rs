@@ -0,0 +1,360 @@
+use json_api::{
+    doc::{Data, Document, Identifier, JsonApi, Link, NewObject, Object, Relationship, Version},
+    error::ErrorKind,
+    value::{Key, Set},
+    Error,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+#[test]
+fn version_from_str() {
+    assert_eq!("1.0".parse::<Version>().unwrap(), Version::V1);
+    assert_eq!("1.1".parse::<Version>().unwrap(), Version::V1_1);
+    assert!("2.0".parse::<Version>().is_err());
+}
+
+#[test]
+fn jsonapi_round_trip() -> Result<(), Error> {
+    let source = json!({
+        "ext": ["https://jsonapi.org/ext/atomic"],
+        "profile": ["http://example.com/profiles/flexible-pagination"],
+        "version": "1.1",
+    });
+
+    let jsonapi: JsonApi = serde_json::from_value(source.clone())?;
+
+    assert_eq!(jsonapi.version, Version::V1_1);
+    assert_eq!(jsonapi.ext.len(), 1);
+    assert_eq!(jsonapi.profile.len(), 1);
+    assert_eq!(serde_json::to_value(&jsonapi)?, source);
+
+    Ok(())
+}
+
+#[test]
+fn jsonapi_version_defaults_to_v1() -> Result<(), Error> {
+    let jsonapi: JsonApi = serde_json::from_value(json!({}))?;
+
+    assert_eq!(jsonapi, JsonApi::default());
+    assert_eq!(jsonapi.version, Version::V1);
+
+    Ok(())
+}
+
+#[test]
+fn document_from_v1_1() -> Result<(), Error> {
+    let doc: Document<Object> = serde_json::from_value(json!({
+        "jsonapi": {
+            "version": "1.1",
+            "ext": ["https://jsonapi.org/ext/atomic"],
+        },
+        "links": {
+            "self": {
+                "href": "http://example.com/articles/1",
+                "title": "Article",
+                "type": "application/vnd.api+json",
+            },
+        },
+        "data": {
+            "type": "articles",
+            "id": "1",
+        },
+    }))?;
+
+    assert_eq!(doc.jsonapi().version, Version::V1_1);
+
+    Ok(())
+}
+
+#[test]
+fn link_object_round_trip() -> Result<(), Error> {
+    let source = json!({
+        "href": "http://example.com/articles/1",
+        "rel": "canonical",
+        "describedby": "http://example.com/schemas/articles",
+        "title": "Article",
+        "type": "application/vnd.api+json",
+        "hreflang": ["en", "de"],
+        "meta": { "count": 1 },
+    });
+
+    let link: Link = serde_json::from_value(source.clone())?;
+
+    assert_eq!(link.rel.as_deref(), Some("canonical"));
+    assert_eq!(link.title.as_deref(), Some("Article"));
+    assert_eq!(link.kind.as_deref(), Some("application/vnd.api+json"));
+    assert_eq!(link.hreflang, vec!["en", "de"]);
+    assert_eq!(serde_json::to_value(&link)?, source);
+
+    let link: Link = serde_json::from_value(json!({
+        "href": "http://example.com/articles/1",
+        "hreflang": "en",
+    }))?;
+
+    assert_eq!(link.hreflang, vec!["en"]);
+    assert_eq!(
+        serde_json::to_value(&link)?,
+        json!({ "href": "http://example.com/articles/1", "hreflang": "en" })
+    );
+
+    let link: Link = serde_json::from_value(json!({ "href": "/articles/1" }))?;
+    assert_eq!(serde_json::to_value(&link)?, json!("/articles/1"));
+
+    Ok(())
+}
+
+#[test]
+fn to_doc_advertises_required_version() -> Result<(), Error> {
+    let obj = Object::new("articles".parse()?, "1".to_owned());
+    let mut doc: Document<Object> = json_api::to_doc(obj, None)?;
+
+    assert_eq!(doc.jsonapi().version, Version::V1);
+
+    doc.jsonapi_mut()
+        .profile
+        .insert("http://example.com/profiles/flexible-pagination".parse()?);
+
+    let doc = json_api::to_doc(doc, None)?;
+    assert_eq!(doc.jsonapi().version, Version::V1_1);
+
+    Ok(())
+}
+
+#[test]
+fn identifier_lid_equality() -> Result<(), Error> {
+    let kind = "users".parse::<Key>()?;
+    let by_id = Identifier::new(kind.clone(), "1".to_owned());
+    let by_lid = Identifier::with_lid(kind.clone(), "1".to_owned());
+    let mut set = Set::new();
+
+    assert!(by_id != by_lid);
+    assert!(by_lid == Identifier::with_lid(kind.clone(), "1".to_owned()));
+
+    set.insert(by_id.clone());
+    set.insert(by_lid.clone());
+    set.insert(Identifier::with_lid(kind.clone(), "1".to_owned()));
+    assert_eq!(set.len(), 2);
+
+    let mut obj = Object::new(kind, "2".to_owned());
+    assert!(by_lid != obj);
+
+    obj.lid = Some("1".to_owned());
+    assert!(by_lid == obj);
+
+    Ok(())
+}
+
+#[test]
+fn new_object_flatten_lid() -> Result<(), Error> {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Article {
+        title: String,
+        author: String,
+        comments: Vec<String>,
+    }
+
+    let article: Article = json_api::from_str::<NewObject, _>(
+        &json!({
+            "data": {
+                "type": "articles",
+                "attributes": { "title": "Rails is Omakase" },
+                "relationships": {
+                    "author": { "data": { "type": "people", "lid": "a" } },
+                    "comments": {
+                        "data": [
+                            { "type": "comments", "id": "5" },
+                            { "type": "comments", "lid": "b" },
+                        ],
+                    },
+                },
+            },
+            "included": [
+                {
+                    "type": "people",
+                    "id": "9",
+                    "lid": "a",
+                    "attributes": { "name": "dgeb" },
+                },
+                {
+                    "type": "comments",
+                    "id": "12",
+                    "lid": "b",
+                    "attributes": { "body": "First!" },
+                },
+            ],
+        })
+        .to_string(),
+    )?;
+
+    // Linkage with a `lid` has the same shape as linkage with an `id`.
+    assert_eq!(
+        article,
+        Article {
+            title: "Rails is Omakase".to_owned(),
+            author: "9".to_owned(),
+            comments: vec!["5".to_owned(), "12".to_owned()],
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn new_object_unresolved_lid() {
+    let result = json_api::from_str::<NewObject, serde_json::Value>(
+        &json!({
+            "data": {
+                "type": "articles",
+                "relationships": {
+                    "author": { "data": { "type": "people", "lid": "a" } },
+                },
+            },
+        })
+        .to_string(),
+    );
+
+    match result.map_err(|e| e.error_source().and_then(|source| source.pointer)) {
+        Err(Some(ref pointer)) => assert_eq!(pointer, "/data/relationships/author/data"),
+        other => panic!("expected an invalid member error, found {:?}", other),
+    }
+}
+
+#[test]
+fn object_cyclic_lid() {
+    let result = json_api::from_str::<Object, serde_json::Value>(
+        &json!({
+            "data": {
+                "type": "articles",
+                "id": "1",
+                "relationships": {
+                    "author": { "data": { "type": "people", "lid": "a" } },
+                },
+            },
+            "included": [{
+                "type": "people",
+                "id": "9",
+                "lid": "a",
+                "relationships": {
+                    "mentor": { "data": { "type": "people", "lid": "a" } },
+                },
+            }],
+        })
+        .to_string(),
+    );
+
+    match result.map_err(|e| e.error_source().and_then(|source| source.pointer)) {
+        Err(Some(ref pointer)) => {
+            assert_eq!(pointer, "/included/0/relationships/mentor/data");
+        }
+        other => panic!("expected an invalid member error, found {:?}", other),
+    }
+}
+
+#[test]
+fn identifier_without_id_or_lid() {
+    let result = serde_json::from_value::<Identifier>(json!({ "type": "people" }));
+    assert!(result.is_err());
+
+    let ident = serde_json::from_value::<Identifier>(json!({ "type": "people", "lid": "a" }));
+    assert_eq!(ident.ok().and_then(|ident| ident.lid), Some("a".to_owned()));
+}
+
+#[test]
+fn meta_only_document() -> Result<(), Error> {
+    let source = json!({ "meta": { "archived": true } });
+    let doc = serde_json::from_value::<Document<Identifier>>(source)?;
+
+    match doc {
+        Document::Meta { ref meta, .. } => assert_eq!(meta.len(), 1),
+        ref other => panic!("expected a meta document, found {:?}", other),
+    }
+
+    assert!(doc.is_ok());
+    assert_eq!(
+        serde_json::to_value(json_api::to_doc::<_, Identifier>(doc, None)?)?,
+        json!({ "jsonapi": { "version": "1.0" }, "meta": { "archived": true } })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn relationship_data_absent_or_null() -> Result<(), Error> {
+    let absent: Relationship = serde_json::from_value(json!({
+        "links": { "related": "/articles/1/comments" },
+    }))?;
+
+    let null: Relationship = serde_json::from_value(json!({ "data": null }))?;
+
+    assert_eq!(absent.data, None);
+    assert_eq!(null.data, Some(Data::Member(Box::new(None))));
+    assert_eq!(
+        serde_json::to_value(&absent)?,
+        json!({ "links": { "related": "/articles/1/comments" } })
+    );
+    assert_eq!(serde_json::to_value(&null)?, json!({ "data": null }));
+
+    Ok(())
+}
+
+fn cyclic_document() -> serde_json::Value {
+    json!({
+        "data": {
+            "type": "posts",
+            "id": "1",
+            "relationships": {
+                "author": { "data": { "type": "users", "id": "2" } },
+            },
+        },
+        "included": [{
+            "type": "users",
+            "id": "2",
+            "relationships": {
+                "posts": { "data": [{ "type": "posts", "id": "3" }] },
+            },
+        }, {
+            "type": "posts",
+            "id": "3",
+            "relationships": {
+                "author": { "data": { "type": "users", "id": "2" } },
+            },
+        }],
+    })
+}
+
+#[test]
+fn from_doc_cyclic_relationships() -> Result<(), Error> {
+    let doc = cyclic_document().to_string();
+    let value: serde_json::Value = json_api::from_str::<Object, _>(&doc)?;
+
+    assert_eq!(
+        value,
+        json!({
+            "id": "1",
+            "author": {
+                "id": "2",
+                "posts": [{ "id": "3", "author": "2" }],
+            },
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn from_doc_max_depth() -> Result<(), Error> {
+    let doc = cyclic_document().to_string();
+    let value: serde_json::Value = json_api::doc::from_str_with_depth::<Object, _>(&doc, 2)?;
+
+    assert_eq!(value["author"]["posts"][0]["author"], "2");
+
+    let result = json_api::doc::from_str_with_depth::<Object, serde_json::Value>(&doc, 1);
+
+    match result.map_err(|e| e.0) {
+        Err(ErrorKind::DepthLimitExceeded(1)) => {}
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use json_api::{
+    doc::{ErrorObject, Identifier, NewObject},
+    error::ErrorKind,
+    http::{header, Request, StatusCode},
+    http_ext, Error, Value,
+};
+
+const MEDIA_TYPE: &str = "application/vnd.api+json";
+
+fn request(content_type: &str, body: &'static str) -> Request<&'static str> {
+    Request::post("/posts")
+        .header(header::CONTENT_TYPE, content_type)
+        .body(body)
+        .unwrap()
+}
+
+#[test]
+fn decode_checks_headers() {
+    let req = request("application/json", "{}");
+    let e = http_ext::decode::<NewObject, Value, _>(&req).unwrap_err();
+
+    assert_eq!(e.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let mut req = request(MEDIA_TYPE, "{}");
+    let value = format!("{}; charset=utf-8", MEDIA_TYPE);
+
+    req.headers_mut()
+        .insert(header::ACCEPT, value.parse().unwrap());
+
+    let e = http_ext::decode::<NewObject, Value, _>(&req).unwrap_err();
+    assert_eq!(e.status(), StatusCode::NOT_ACCEPTABLE);
+}
+
+#[test]
+fn decode_invalid_json() {
+    let req = request(MEDIA_TYPE, r#"{"data":"#);
+    let e = http_ext::decode::<NewObject, Value, _>(&req).unwrap_err();
+
+    match *e.kind() {
+        ErrorKind::InvalidMember(ref pointer, _) => assert_eq!(pointer, ""),
+        ref kind => panic!("unexpected error: {}", kind),
+    }
+
+    assert_eq!(e.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn query_from_uri() -> Result<(), Error> {
+    let req = Request::get("/posts?fields[posts]=title").body(()).unwrap();
+    assert_eq!(
+        http_ext::query(req.uri())?,
+        json_api::query::from_str("fields[posts]=title")?
+    );
+
+    let req = Request::get("/posts").body(()).unwrap();
+    assert_eq!(http_ext::query(req.uri())?, Default::default());
+
+    let req = Request::get("/posts?page[number]=2&page[offset]=10")
+        .body(())
+        .unwrap();
+
+    assert!(http_ext::query(req.uri()).is_err());
+    Ok(())
+}
+
+#[test]
+fn response() -> Result<(), Error> {
+    let ident = Identifier::new("posts".parse()?, "1".to_owned());
+    let resp = http_ext::response(ident, None)?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], MEDIA_TYPE);
+
+    let body: serde_json::Value = serde_json::from_slice(resp.body())?;
+    assert_eq!(body["data"]["id"], "1");
+
+    Ok(())
+}
+
+#[test]
+fn error_response() -> Result<(), Error> {
+    let resp = http_ext::error_response(&Error::invalid_parameter("sort", "unknown"));
+    let body: serde_json::Value = serde_json::from_slice(resp.body())?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], MEDIA_TYPE);
+    assert_eq!(body["errors"][0]["source"]["parameter"], "sort");
+
+    let resp = http_ext::error_response(&Error::from("secret"));
+    let body: serde_json::Value = serde_json::from_slice(resp.body())?;
+
+    assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(body["errors"][0].get("detail").is_none());
+
+    let resp = http_ext::error_response(ErrorObject::new(Some(StatusCode::NOT_FOUND)));
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use json_api::{
+    error::ErrorKind,
+    http::StatusCode,
+    media_type::{self, MediaType},
+    Error,
+};
+
+const ATOMIC: &str = "https://jsonapi.org/ext/atomic";
+
+#[test]
+fn content_type() -> Result<(), Error> {
+    assert_eq!(media_type::content_type(None, &[])?, MediaType::new());
+    assert_eq!(
+        media_type::content_type(Some("application/vnd.api+json"), &[])?,
+        MediaType::new()
+    );
+
+    let media_type = media_type::content_type(
+        Some(r#"application/vnd.api+json;profile="https://example.com/a https://example.com/b""#),
+        &[],
+    )?;
+
+    assert_eq!(
+        media_type.profile,
+        vec!["https://example.com/a", "https://example.com/b"]
+    );
+
+    for value in &[
+        "application/json",
+        "application/vnd.api+json; charset=utf-8",
+        r#"application/vnd.api+json; ext="https://jsonapi.org/ext/atomic""#,
+    ] {
+        let e = media_type::content_type(Some(value), &[]).unwrap_err();
+
+        match *e.kind() {
+            ErrorKind::UnsupportedMediaType(ref actual) => assert_eq!(actual, value),
+            ref kind => panic!("unexpected error: {}", kind),
+        }
+
+        assert_eq!(e.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn accept() -> Result<(), Error> {
+    for value in &[
+        "*/*",
+        "application/json",
+        "application/vnd.api+json",
+        "application/vnd.api+json; q=0.5",
+        "application/vnd.api+json; charset=utf-8, application/vnd.api+json",
+    ] {
+        assert_eq!(media_type::accept(Some(value), &[])?, MediaType::new());
+    }
+
+    let value = format!(r#"application/vnd.api+json; ext="{}""#, ATOMIC);
+    let media_type = media_type::accept(Some(&value), &[ATOMIC])?;
+
+    assert_eq!(media_type.ext, vec![ATOMIC]);
+    assert_eq!(
+        media_type.to_string(),
+        value.replace("; ", ";").replace(";", "; ")
+    );
+
+    for value in &[
+        "application/vnd.api+json; charset=utf-8",
+        "application/vnd.api+json; charset=utf-8, text/html",
+        value.as_str(),
+    ] {
+        let e = media_type::accept(Some(value), &[]).unwrap_err();
+        assert_eq!(e.status(), StatusCode::NOT_ACCEPTABLE);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn negotiate() -> Result<(), Error> {
+    let value = format!(r#"application/vnd.api+json; ext="{}""#, ATOMIC);
+    let negotiated = media_type::negotiate(Some(&value), None, &[ATOMIC])?;
+
+    assert_eq!(negotiated.content_type.ext, vec![ATOMIC]);
+    assert_eq!(negotiated.accept, MediaType::new());
+
+    let e = media_type::negotiate(None, Some(&value), &[]).unwrap_err();
+    assert_eq!(e.status(), StatusCode::NOT_ACCEPTABLE);
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use json_api::{
+    http::StatusCode,
+    query::{self, Page},
+    view::{pagination_links, Pagination},
+    Error,
+};
+
+fn links(source: &str, pagination: &Pagination) -> Result<Vec<(String, String)>, Error> {
+    let query = query::from_str(source)?;
+    let links = pagination_links("/posts", &query, pagination)?;
+
+    Ok(links
+        .iter()
+        .map(|(key, link)| (key.to_string(), link.to_string()))
+        .collect())
+}
+
+fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    expected
+        .iter()
+        .map(|&(key, href)| (key.to_owned(), href.to_owned()))
+        .collect()
+}
+
+#[test]
+fn number_links() -> Result<(), Error> {
+    assert_eq!(
+        links("sort=-title&page[size]=10", &Pagination::Total(25))?,
+        pairs(&[
+            ("first", "/posts?page[size]=10&sort=-title"),
+            ("next", "/posts?page[number]=2&page[size]=10&sort=-title"),
+            ("last", "/posts?page[number]=3&page[size]=10&sort=-title"),
+        ])
+    );
+
+    assert_eq!(
+        links("", &Pagination::Total(25))?,
+        pairs(&[("first", "/posts"), ("last", "/posts")])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn offset_links() -> Result<(), Error> {
+    assert_eq!(
+        links("page[offset]=15&page[limit]=10", &Pagination::Total(30))?,
+        pairs(&[
+            ("first", "/posts?page[offset]=0&page[limit]=10"),
+            ("prev", "/posts?page[offset]=5&page[limit]=10"),
+            ("next", "/posts?page[offset]=25&page[limit]=10"),
+            ("last", "/posts?page[offset]=20&page[limit]=10"),
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cursor_links() -> Result<(), Error> {
+    let pagination = Pagination::Cursor {
+        first: Some("a".to_owned()),
+        last: Some("j".to_owned()),
+        has_next: true,
+        has_prev: false,
+    };
+
+    assert_eq!(
+        links("page[after]=0&page[size]=10", &pagination)?,
+        pairs(&[
+            ("first", "/posts?page[size]=10"),
+            ("next", "/posts?page[after]=j&page[size]=10"),
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cursor_first_page() -> Result<(), Error> {
+    let pagination = Pagination::Cursor {
+        first: Some("a".to_owned()),
+        last: Some("j".to_owned()),
+        has_next: true,
+        has_prev: false,
+    };
+
+    assert_eq!(
+        links("", &pagination)?,
+        pairs(&[("first", "/posts"), ("next", "/posts?page[after]=j")])
+    );
+
+    let first = links("page[after]=0&page[size]=10", &pagination)?
+        .into_iter()
+        .find(|(key, _)| key == "first")
+        .map(|(_, href)| href)
+        .unwrap();
+    let (_, source) = first.split_once('?').unwrap();
+
+    assert_eq!(
+        links(source, &pagination)?,
+        pairs(&[
+            ("first", "/posts?page[size]=10"),
+            ("next", "/posts?page[after]=j&page[size]=10"),
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cursor_links_round_trip() -> Result<(), Error> {
+    let pagination = Pagination::Cursor {
+        first: Some("a+b/c=d&e".to_owned()),
+        last: Some("z%20y==".to_owned()),
+        has_next: true,
+        has_prev: true,
+    };
+
+    let expected = [
+        ("first", Page::size_only(10)),
+        (
+            "prev",
+            Page::cursor(None, Some("a+b/c=d&e".to_owned()), Some(10)),
+        ),
+        (
+            "next",
+            Page::cursor(Some("z%20y==".to_owned()), None, Some(10)),
+        ),
+    ];
+
+    let links = links("page[after]=x&page[size]=10", &pagination)?;
+    assert_eq!(links.len(), expected.len());
+
+    for ((name, href), (key, page)) in links.iter().zip(&expected) {
+        let source = href.split_once('?').map_or("", |(_, source)| source);
+
+        assert_eq!(name, key);
+        assert_eq!(
+            query::from_str(source)?.page.as_ref(),
+            Some(page),
+            "{}",
+            href
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn mismatched_pagination() {
+    let e = links("page[after]=0", &Pagination::Total(10)).unwrap_err();
+    assert_eq!(e.status(), StatusCode::BAD_REQUEST);
+}
@@ -1,6 +1,7 @@
 use indexmap::{indexmap, IndexMap};
 use json_api::{
-    query::{self, Direction, Query},
+    error::ErrorKind,
+    query::{self, Direction, Filter, Limits, Operator, Query},
     Error,
 };
 
@@ -39,11 +40,26 @@ fn from_mapping() -> Result<Mapping, Error> {
             .page(1, None)
             .build()?,
         "page[size]=10" => Query::builder()
-            .page(1, Some(10))
+            .page_size(10)
             .build()?,
         "page[number]=2&page[size]=15" => Query::builder()
             .page(2, Some(15))
             .build()?,
+        "page[offset]=0" => Query::builder()
+            .page_offset(0, None)
+            .build()?,
+        "page[offset]=20&page[limit]=10" => Query::builder()
+            .page_offset(20, Some(10))
+            .build()?,
+        "page[after]=abc&page[size]=10" => Query::builder()
+            .page_cursor(Some("abc"), None, Some(10))
+            .build()?,
+        "page[after]=a%2Bb%2Fc%3Dd%26e%25&page[size]=10" => Query::builder()
+            .page_cursor(Some("a+b/c=d&e%"), None, Some(10))
+            .build()?,
+        "page[after]=abc&page[before]=xyz" => Query::builder()
+            .page_cursor(Some("abc"), Some("xyz"), None)
+            .build()?,
         "sort=-published-at" => Query::builder()
             .sort("published-at", Direction::Desc)
             .build()?,
@@ -123,3 +139,200 @@ fn query_to_vec() {
         assert_eq!(actual, expected.to_owned().into_bytes());
     }
 }
+
+#[test]
+fn filter_from_str() -> Result<(), Error> {
+    let query = query::from_str(concat!(
+        "filter[name]=Alfred&",
+        "filter[age][gte]=30&filter[age][lt]=40&",
+        "filter[deleted-at][null]=true&",
+        "filter[or][0][role]=admin,owner&",
+        "filter[or][1][email][like]=%25wayne.com",
+    ))?;
+
+    assert_eq!(
+        query.parse_filter()?,
+        Filter::And(vec![
+            Filter::And(vec![
+                Filter::Field("age".parse()?, Operator::Gte("30".into())),
+                Filter::Field("age".parse()?, Operator::Lt("40".into())),
+            ]),
+            Filter::Field("deleted-at".parse()?, Operator::Null(true)),
+            Filter::Field("name".parse()?, Operator::Eq("Alfred".into())),
+            Filter::Or(vec![
+                Filter::Field(
+                    "role".parse()?,
+                    Operator::In(vec!["admin".into(), "owner".into()]),
+                ),
+                Filter::Field("email".parse()?, Operator::Like("%wayne.com".into())),
+            ]),
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn filter_round_trip() -> Result<(), Error> {
+    let filter = Filter::And(vec![
+        Filter::Field("name".parse()?, Operator::Eq("Alfred,Bruce".into())),
+        Filter::Field("age".parse()?, Operator::Gt("30".into())),
+        Filter::Field("age".parse()?, Operator::Gt("40".into())),
+        Filter::Field(
+            "users.role".parse()?,
+            Operator::In(vec!["admin".into(), "owner".into()]),
+        ),
+        Filter::Or(vec![
+            Filter::Field("deleted-at".parse()?, Operator::Null(false)),
+            Filter::And(vec![
+                Filter::Field("age".parse()?, Operator::Ne("1".into())),
+                Filter::Field("name".parse()?, Operator::Eq("Bruce".into())),
+            ]),
+        ]),
+    ]);
+
+    let mut query = Query::new();
+    query.set_filter(&filter);
+
+    // Members are ordered by name when a query string is decoded.
+    let query = query::from_str(&query::to_string(&query)?)?;
+
+    assert_eq!(
+        query.parse_filter()?,
+        Filter::And(vec![
+            Filter::Field("age".parse()?, Operator::Gt("30".into())),
+            Filter::And(vec![Filter::Field(
+                "age".parse()?,
+                Operator::Gt("40".into()),
+            )]),
+            Filter::Field("name".parse()?, Operator::Eq("Alfred,Bruce".into())),
+            Filter::Or(vec![
+                Filter::Field("deleted-at".parse()?, Operator::Null(false)),
+                Filter::And(vec![
+                    Filter::Field("age".parse()?, Operator::Ne("1".into())),
+                    Filter::Field("name".parse()?, Operator::Eq("Bruce".into())),
+                ]),
+            ]),
+            Filter::Field(
+                "users.role".parse()?,
+                Operator::In(vec!["admin".into(), "owner".into()]),
+            ),
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn filter_errors() -> Result<(), Error> {
+    let cases = [
+        ("filter[age][between]=1", "filter[age][between]"),
+        ("filter[deleted-at][null]=maybe", "filter[deleted-at][null]"),
+        (
+            "filter[or][0][age][between]=1",
+            "filter[or][0][age][between]",
+        ),
+        ("filter[or]=1", "filter[or]"),
+    ];
+
+    for &(source, parameter) in &cases {
+        let e = query::from_str(source)?.parse_filter().unwrap_err();
+
+        match *e.kind() {
+            ErrorKind::InvalidParameter(ref value, _) => assert_eq!(value, parameter),
+            ref kind => panic!("unexpected error: {}", kind),
+        }
+
+        let source = e.error_source().unwrap();
+        assert_eq!(source.parameter.as_deref(), Some(parameter));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn page_mixed_strategies() {
+    for source in &[
+        "page[number]=2&page[offset]=10",
+        "page[after]=abc&page[number]=2",
+        "page[before]=abc&page[limit]=10",
+    ] {
+        assert!(query::from_str(source).is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn page_encoded_cursor() -> Result<(), Error> {
+    let expected = Query::builder()
+        .page_cursor(None, Some("x=1&y=2"), Some(5))
+        .build()?;
+
+    for source in &[
+        "page[before]=x%3D1%26y%3D2&page[size]=5",
+        "page%5Bbefore%5D=x%3D1%26y%3D2&page%5bsize%5d=5",
+    ] {
+        assert_eq!(query::from_str(source)?, expected, "{}", source);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn include_limits() -> Result<(), Error> {
+    let limits = Limits {
+        max_depth: Some(2),
+        max_paths: Some(2),
+        ..Default::default()
+    };
+
+    let query = query::from_str_with_limits("include=author,comments.author", limits)?;
+    assert_eq!(query, query::from_str("include=author,comments.author")?);
+
+    for &(source, name) in &[
+        ("include=comments.author.posts", "depth"),
+        ("include=author,comments,comments.author", "number of paths"),
+    ] {
+        let e = query::from_str_with_limits(source, limits).unwrap_err();
+
+        match *e.kind() {
+            ErrorKind::IncludeLimitExceeded(ref value, 2) => assert_eq!(value, name),
+            ref kind => panic!("unexpected error: {}", kind),
+        }
+
+        assert_eq!(
+            e.error_source().and_then(|source| source.parameter),
+            Some("include".to_owned())
+        );
+    }
+
+    let built = Query::builder()
+        .include("comments.author.posts")
+        .limits(limits)
+        .build();
+
+    assert!(built.is_err());
+    Ok(())
+}
+
+#[test]
+fn invalid_parameter_source() {
+    for &(source, parameter) in &[
+        ("page[number]=abc", "page[number]"),
+        ("include=author&page[size]=-1", "page[size]"),
+        ("page[number]=2&page[offset]=10", "page"),
+        (
+            "sort=title&page[number]=2&include=author&page[size]=abc",
+            "page[size]",
+        ),
+    ] {
+        let e = query::from_str(source).unwrap_err();
+
+        assert_eq!(e.status().as_u16(), 400, "{}", source);
+        assert_eq!(
+            e.error_source().and_then(|source| source.parameter),
+            Some(parameter.to_owned()),
+            "{}",
+            source
+        );
+    }
+}
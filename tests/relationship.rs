@@ -0,0 +1,86 @@
+use json_api::{
+    doc::Identifier,
+    view::{relationship_links, Linkage},
+    Error,
+};
+use serde_json::json;
+
+fn links(path: &str) -> Result<Vec<(String, String)>, Error> {
+    Ok(relationship_links(path)?
+        .iter()
+        .map(|(key, link)| (key.to_string(), link.to_string()))
+        .collect())
+}
+
+#[test]
+fn related_link_drops_relationships_segment() -> Result<(), Error> {
+    assert_eq!(
+        links("/posts/1/relationships/comments")?,
+        vec![
+            (
+                "self".to_owned(),
+                "/posts/1/relationships/comments".to_owned()
+            ),
+            ("related".to_owned(), "/posts/1/comments".to_owned()),
+        ]
+    );
+
+    assert_eq!(
+        links("/api/posts/1/relationships/author/")?,
+        vec![
+            (
+                "self".to_owned(),
+                "/api/posts/1/relationships/author/".to_owned()
+            ),
+            ("related".to_owned(), "/api/posts/1/author/".to_owned()),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn related_link_keeps_scheme_and_authority() -> Result<(), Error> {
+    assert_eq!(
+        links("http://example.com:8080/posts/1/relationships/author")?,
+        vec![
+            (
+                "self".to_owned(),
+                "http://example.com:8080/posts/1/relationships/author".to_owned()
+            ),
+            (
+                "related".to_owned(),
+                "http://example.com:8080/posts/1/author".to_owned()
+            ),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn path_without_relationships_segment() -> Result<(), Error> {
+    assert_eq!(
+        links("/posts/1/comments")?,
+        vec![("self".to_owned(), "/posts/1/comments".to_owned())]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn linkage_renders_links() -> Result<(), Error> {
+    let value = Linkage::new(None::<Identifier>, "/posts/1/relationships/author");
+    let doc = serde_json::to_value(json_api::to_doc::<_, Identifier>(value, None)?)?;
+
+    assert_eq!(doc["data"], serde_json::Value::Null);
+    assert_eq!(
+        doc["links"],
+        json!({
+            "self": "/posts/1/relationships/author",
+            "related": "/posts/1/author",
+        })
+    );
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use json_api::{
+    doc::{self, Document, FromObject, Object, Resolver},
+    error::ErrorKind,
+    Error,
+};
+use serde_json::json;
+
+#[derive(Debug)]
+struct Person {
+    name: String,
+    friends: Vec<Person>,
+}
+
+impl FromObject for Person {
+    fn from_object(object: &Object, resolver: &Resolver) -> Result<Self, Error> {
+        Ok(Person {
+            name: resolver.attribute(object, "name")?,
+            friends: resolver.has_many(object, "friends")?,
+        })
+    }
+}
+
+fn document(value: serde_json::Value) -> Result<Document<Object>, Error> {
+    Ok(serde_json::from_value(value)?)
+}
+
+fn pointer(e: &Error) -> &str {
+    match *e.kind() {
+        ErrorKind::InvalidMember(ref pointer, _) | ErrorKind::MissingMember(ref pointer) => pointer,
+        ref kind => panic!("unexpected error: {}", kind),
+    }
+}
+
+#[test]
+fn resolve_included() -> Result<(), Error> {
+    let doc = document(json!({
+        "data": [{
+            "type": "people",
+            "id": "1",
+            "attributes": { "name": "Alice" },
+            "relationships": {
+                "friends": { "data": [{ "type": "people", "id": "2" }] },
+            },
+        }, {
+            "type": "people",
+            "id": "3",
+            "attributes": { "name": "Carol" },
+        }],
+        "included": [{
+            "type": "people",
+            "id": "2",
+            "attributes": { "name": "Bob" },
+            "relationships": {
+                "friends": { "data": [{ "type": "people", "id": "3" }] },
+            },
+        }],
+    }))?;
+
+    let people: Vec<Person> = doc::resolve_many(&doc)?;
+
+    assert_eq!(people.len(), 2);
+    assert_eq!(people[0].friends[0].name, "Bob");
+    assert_eq!(people[0].friends[0].friends[0].name, "Carol");
+    assert!(people[1].friends.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn resolve_errors() -> Result<(), Error> {
+    let doc = document(json!({
+        "data": {
+            "type": "people",
+            "id": "1",
+            "relationships": {
+                "friends": { "data": [{ "type": "people", "id": "2" }] },
+            },
+        },
+        "included": [{ "type": "people", "id": "2", "attributes": { "name": 1 } }],
+    }))?;
+
+    let e = doc::resolve_one::<Person>(&doc).unwrap_err();
+    assert_eq!(pointer(&e), "/data/attributes/name");
+
+    let doc = document(json!({
+        "data": {
+            "type": "people",
+            "id": "1",
+            "attributes": { "name": "Alice" },
+            "relationships": {
+                "friends": { "data": [{ "type": "people", "id": "2" }] },
+            },
+        },
+        "included": [{ "type": "people", "id": "2", "attributes": { "name": 1 } }],
+    }))?;
+
+    let e = doc::resolve_one::<Person>(&doc).unwrap_err();
+    assert_eq!(pointer(&e), "/included/0/attributes/name");
+
+    let doc = document(json!({
+        "data": {
+            "type": "people",
+            "id": "1",
+            "attributes": { "name": "Alice" },
+            "relationships": {
+                "friends": { "data": [{ "type": "people", "id": "4" }] },
+            },
+        },
+    }))?;
+
+    let e = doc::resolve_one::<Person>(&doc).unwrap_err();
+    assert_eq!(pointer(&e), "/data/relationships/friends/data/0");
+
+    Ok(())
+}
+
+#[test]
+fn resolve_cycle() -> Result<(), Error> {
+    let doc = document(json!({
+        "data": {
+            "type": "people",
+            "id": "1",
+            "attributes": { "name": "Alice" },
+            "relationships": {
+                "friends": { "data": [{ "type": "people", "id": "2" }] },
+            },
+        },
+        "included": [{
+            "type": "people",
+            "id": "2",
+            "attributes": { "name": "Bob" },
+            "relationships": {
+                "friends": { "data": [{ "type": "people", "id": "2" }] },
+            },
+        }],
+    }))?;
+
+    let e = doc::resolve_one::<Person>(&doc).unwrap_err();
+    assert_eq!(pointer(&e), "/included/0/relationships/friends/data/0");
+
+    Ok(())
+}
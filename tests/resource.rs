@@ -0,0 +1,397 @@
+#[macro_use]
+extern crate json_api;
+
+use json_api::{
+    doc::{Identifier, Object, Relationship},
+    error::ErrorKind,
+    value::{Key, Path},
+    view::{Context, Limited},
+    Error, Resource,
+};
+use serde_json::json;
+
+struct Comment {
+    id: u64,
+    body: String,
+}
+
+resource!(Comment, |&self| {
+    kind "comments";
+    id self.id;
+
+    attrs body;
+});
+
+struct Post {
+    id: u64,
+    title: String,
+    comments: Vec<Comment>,
+}
+
+resource!(Post, |&self| {
+    kind "posts";
+    id self.id;
+
+    attrs title;
+
+    has_many "comments", {
+        data self.comments.iter();
+    }
+
+    has_many "likes", {
+        link "related", format!("/posts/{}/likes", self.id);
+        meta "count", 12;
+    }
+});
+
+fn post() -> Post {
+    Post {
+        id: 1,
+        title: "Hello, World!".to_owned(),
+        comments: vec![Comment {
+            id: 2,
+            body: "First!".to_owned(),
+        }],
+    }
+}
+
+#[test]
+fn relationship_without_data() -> Result<(), Error> {
+    let value = serde_json::to_value(json_api::to_doc::<_, Object>(&post(), None)?)?;
+
+    assert_eq!(
+        value["data"]["relationships"],
+        json!({
+            "comments": {
+                "data": [{ "type": "comments", "id": "2" }],
+            },
+            "likes": {
+                "links": { "related": "/posts/1/likes" },
+                "meta": { "count": 12 },
+            },
+        })
+    );
+
+    Ok(())
+}
+
+struct Person {
+    id: u64,
+    friends: Vec<Person>,
+}
+
+resource!(Person, |&self| {
+    kind "people";
+    id self.id;
+
+    has_many friends;
+});
+
+fn person(id: u64, friends: Vec<Person>) -> Person {
+    Person { id, friends }
+}
+
+#[test]
+fn included_excludes_primary_data() -> Result<(), Error> {
+    let people = [
+        person(1, vec![person(2, vec![]), person(3, vec![])]),
+        person(2, vec![]),
+    ];
+
+    let query = json_api::query::from_str("include=friends")?;
+    let value = serde_json::to_value(json_api::to_doc::<_, Object>(&people[..], Some(&query))?)?;
+
+    assert_eq!(
+        value["included"],
+        json!([{
+            "type": "people",
+            "id": "3",
+            "relationships": { "friends": { "data": [] } },
+        }])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn missing_include_ignored() -> Result<(), Error> {
+    let query = json_api::query::from_str("include=author,comments")?;
+    let value = serde_json::to_value(json_api::to_doc::<_, Object>(&post(), Some(&query))?)?;
+
+    assert_eq!(
+        value["included"],
+        json!([{ "type": "comments", "id": "2", "attributes": { "body": "First!" } }])
+    );
+
+    Ok(())
+}
+
+struct Article(u64);
+
+impl Resource for Article {
+    fn kind() -> Key {
+        Key::from_raw("articles".to_owned())
+    }
+
+    fn id(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn to_ident(&self, _: &mut Context) -> Result<Identifier, Error> {
+        Ok(Identifier::new(Self::kind(), self.id()))
+    }
+
+    fn to_object(&self, ctx: &mut Context) -> Result<Object, Error> {
+        let mut obj = Object::new(Self::kind(), self.id());
+
+        if ctx.field("author") {
+            obj.relationships
+                .insert("author".parse()?, Relationship::default());
+        }
+
+        Ok(obj)
+    }
+
+    fn missing_include(path: &Path) -> Result<(), Error> {
+        let reason = format!(r#"the relationship "{}" does not exist"#, path);
+        Err(Error::invalid_parameter("include", &reason))
+    }
+}
+
+#[test]
+fn missing_include_excluded_by_fields() -> Result<(), Error> {
+    let render = |source: &str| -> Result<_, Error> {
+        let query = json_api::query::from_str(source)?;
+        Ok(json_api::to_doc::<_, Object>(&Article(1), Some(&query)).is_ok())
+    };
+
+    assert!(render("include=author")?);
+    assert!(render("fields[articles]=title&include=author")?);
+    assert!(!render("include=editor")?);
+
+    Ok(())
+}
+
+/// A post that renders its comments before it applies the sparse fieldset of the
+/// request, so a comment can be included without being linked.
+struct Draft(Post);
+
+impl Resource for Draft {
+    fn kind() -> Key {
+        Post::kind()
+    }
+
+    fn id(&self) -> String {
+        self.0.id()
+    }
+
+    fn to_ident(&self, ctx: &mut Context) -> Result<Identifier, Error> {
+        self.0.to_ident(ctx)
+    }
+
+    fn to_object(&self, ctx: &mut Context) -> Result<Object, Error> {
+        let key = "comments".parse::<Key>()?;
+        let mut obj = Object::new(Self::kind(), self.id());
+        let mut data = Vec::new();
+
+        {
+            let mut ctx = ctx.fork(Comment::kind(), &key);
+
+            for comment in &self.0.comments {
+                data.push(comment.to_ident(&mut ctx)?);
+
+                if ctx.included() {
+                    let object = comment.to_object(&mut ctx)?;
+                    ctx.include(object)?;
+                }
+            }
+        }
+
+        if ctx.field("comments") {
+            obj.relationships.insert(key, Relationship::from(data));
+        }
+
+        Ok(obj)
+    }
+}
+
+#[test]
+fn included_unreachable_through_fields() -> Result<(), Error> {
+    let render = |source: &str| -> Result<_, Error> {
+        let query = json_api::query::from_str(source)?;
+        let doc = json_api::to_doc::<_, Object>(&Draft(post()), Some(&query))?;
+
+        Ok(serde_json::to_value(doc)?)
+    };
+
+    let doc = render("include=comments")?;
+    assert_eq!(doc["included"][0]["id"], "2");
+
+    // The sparse fieldset removes the relationship that references the comment.
+    let doc = render("fields[posts]=title&include=comments")?;
+    assert!(doc["data"].get("relationships").is_none());
+    assert!(doc.get("included").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn validate_include() -> Result<(), Error> {
+    let validate = |source: &str| -> Result<_, Error> {
+        let query = json_api::query::from_str(source)?;
+        Ok((
+            query.validate_include::<Post>().is_ok(),
+            query.validate_include::<Person>().is_ok(),
+        ))
+    };
+
+    assert_eq!(validate("include=comments,likes")?, (true, false));
+    assert_eq!(validate("include=friends.friends")?, (false, true));
+    assert_eq!(validate("include=friends.posts")?, (false, false));
+    assert_eq!(validate("include=author")?, (false, false));
+
+    // The related resource type of a relationship that is declared with a block is
+    // unknown, so the remaining segments of the path are not checked.
+    assert_eq!(validate("include=comments.post")?, (true, false));
+
+    Ok(())
+}
+
+#[test]
+fn validate_fields() -> Result<(), Error> {
+    let validate = |source: &str| -> Result<_, Error> {
+        let query = json_api::query::from_str(source)?;
+        Ok(query.validate_fields::<Post>())
+    };
+
+    assert!(validate("fields[posts]=title,comments,likes")?.is_ok());
+    assert!(validate("fields[comments]=body")?.is_ok());
+    assert!(validate("fields[users]=name")?.is_ok());
+
+    let error = validate("fields[posts]=titel")?.unwrap_err();
+    let object = json_api::doc::ErrorObject::from(&error);
+
+    assert_eq!(object.status.map(|status| status.as_u16()), Some(400));
+    assert_eq!(
+        object.source.and_then(|source| source.parameter),
+        Some("fields[posts]".to_owned())
+    );
+
+    Ok(())
+}
+
+struct Blog {
+    id: u64,
+    published_at: String,
+    blog_posts: Vec<Post>,
+}
+
+resource!(Blog, |&self| {
+    kind "blogs";
+    id self.id;
+
+    attrs published_at;
+    has_many blog_posts;
+});
+
+#[test]
+fn validate_multi_word_names() -> Result<(), Error> {
+    let query = json_api::query::from_str(
+        "fields[blogs]=published-at,blog-posts&include=blog-posts.comments",
+    )?;
+
+    assert!(query.validate_include::<Blog>().is_ok());
+    assert!(query.validate_fields::<Blog>().is_ok());
+
+    let query = json_api::query::from_str("include=blog-posts.authors")?;
+    assert!(query.validate_include::<Blog>().is_err());
+
+    let blog = Blog {
+        id: 1,
+        published_at: "2017-01-01".to_owned(),
+        blog_posts: vec![post()],
+    };
+    let query = json_api::query::from_str("fields[blogs]=published-at")?;
+    let value = serde_json::to_value(json_api::to_doc::<_, Object>(&blog, Some(&query))?)?;
+
+    assert_eq!(
+        value["data"]["attributes"],
+        json!({ "published-at": "2017-01-01" })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn include_fields() -> Result<(), Error> {
+    let mut query = json_api::query::from_str("fields[posts]=title&include=comments")?;
+    query.include_fields::<Post>();
+
+    let fields = query.fields.get("posts").map(|names| {
+        names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+    });
+
+    assert_eq!(
+        fields,
+        Some(vec!["title".to_owned(), "comments".to_owned()])
+    );
+    Ok(())
+}
+
+#[test]
+fn include_fields_nested() -> Result<(), Error> {
+    let mut query = json_api::query::from_str(
+        "fields[blogs]=published-at&fields[posts]=title&include=blog-posts.comments",
+    )?;
+    query.include_fields::<Blog>();
+
+    let fields = |kind: &str| {
+        query.fields.get(kind).map(|names| {
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        })
+    };
+
+    assert_eq!(
+        fields("blogs"),
+        Some(vec!["published-at".to_owned(), "blog-posts".to_owned()])
+    );
+    assert_eq!(
+        fields("posts"),
+        Some(vec!["title".to_owned(), "comments".to_owned()])
+    );
+
+    assert!(query.validate_fields::<Blog>().is_ok());
+
+    let query = json_api::query::from_str("fields[posts]=titel&include=blog-posts")?;
+    assert!(query.validate_fields::<Blog>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn max_included() -> Result<(), Error> {
+    let people = [person(1, vec![person(2, vec![]), person(3, vec![])])];
+    let limits = json_api::query::Limits {
+        max_included: Some(1),
+        ..Default::default()
+    };
+
+    let query = json_api::query::from_str_with_limits("include=friends", limits)?;
+    assert!(json_api::to_doc::<_, Object>(&people[..], Some(&query)).is_ok());
+
+    let value = Limited::new(&people[..], limits);
+    let e = json_api::to_doc::<_, Object>(value, Some(&query)).unwrap_err();
+
+    match *e.kind() {
+        ErrorKind::IncludeLimitExceeded(_, 1) => {}
+        ref kind => panic!("unexpected error: {}", kind),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use json_api::{
+    doc::{
+        validate::{self, ViolationKind},
+        Document, ErrorObject, Identifier, Object,
+    },
+    Error,
+};
+
+fn violations(source: &str) -> Result<Vec<(ViolationKind, String)>, Error> {
+    let doc: Document<Object> = serde_json::from_str(source)?;
+
+    Ok(validate::document(&doc)
+        .into_iter()
+        .map(|violation| (violation.kind, violation.pointer))
+        .collect())
+}
+
+#[test]
+fn valid_document() -> Result<(), Error> {
+    let source = r#"{
+        "data": [{
+            "type": "posts",
+            "id": "1",
+            "attributes": { "title": "Hello" },
+            "relationships": {
+                "author": { "data": { "type": "users", "id": "1" } }
+            }
+        }],
+        "included": [{
+            "type": "users",
+            "id": "1",
+            "relationships": {
+                "avatar": { "data": { "type": "images", "id": "1" } }
+            }
+        }, {
+            "type": "images",
+            "id": "1"
+        }]
+    }"#;
+
+    assert_eq!(violations(source)?, Vec::new());
+    Ok(())
+}
+
+#[test]
+fn unlinked_resources() -> Result<(), Error> {
+    let source = r#"{
+        "data": {
+            "type": "posts",
+            "id": "1",
+            "relationships": {
+                "author": { "data": { "type": "users", "id": "1" } }
+            }
+        },
+        "included": [
+            { "type": "users", "id": "1" },
+            { "type": "users", "id": "2" }
+        ]
+    }"#;
+
+    assert_eq!(
+        violations(source)?,
+        vec![(ViolationKind::UnlinkedResource, "/included/1".to_owned())]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn identifier_linkage() -> Result<(), Error> {
+    let doc: Document<Identifier> = serde_json::from_str(
+        r#"{
+        "data": [{ "type": "users", "id": "1" }],
+        "included": [
+            { "type": "users", "id": "1" },
+            { "type": "users", "id": "2" }
+        ]
+    }"#,
+    )?;
+
+    let pointers = validate::document(&doc)
+        .into_iter()
+        .map(|violation| violation.pointer)
+        .collect::<Vec<_>>();
+
+    assert_eq!(pointers, vec!["/included/1".to_owned()]);
+    Ok(())
+}
+
+#[test]
+fn duplicate_resources() -> Result<(), Error> {
+    let source = r#"{
+        "data": [
+            { "type": "posts", "id": "1" },
+            { "type": "posts", "id": "1" }
+        ],
+        "included": [{
+            "type": "posts",
+            "id": "1"
+        }]
+    }"#;
+
+    let kind = ViolationKind::DuplicateResource("posts".parse()?, "1".to_owned());
+
+    assert_eq!(
+        violations(source)?,
+        vec![
+            (kind.clone(), "/data/1".to_owned()),
+            (kind, "/included/0".to_owned()),
+            (ViolationKind::UnlinkedResource, "/included/0".to_owned()),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reserved_members() -> Result<(), Error> {
+    let source = r#"{
+        "data": {
+            "type": "posts",
+            "id": "1",
+            "attributes": {
+                "links": "/posts/1",
+                "relationships": [],
+                "title": "Hello"
+            },
+            "relationships": {
+                "title": { "data": null }
+            }
+        }
+    }"#;
+
+    assert_eq!(
+        violations(source)?,
+        vec![
+            (
+                ViolationKind::ReservedMember("links".parse()?),
+                "/data/attributes/links".to_owned(),
+            ),
+            (
+                ViolationKind::ReservedMember("relationships".parse()?),
+                "/data/attributes/relationships".to_owned(),
+            ),
+            (
+                ViolationKind::FieldConflict("title".parse()?),
+                "/data/relationships/title".to_owned(),
+            ),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn violation_into_error_object() -> Result<(), Error> {
+    let source = r#"{
+        "data": { "type": "posts", "id": "1" },
+        "included": [{ "type": "users", "id": "1" }]
+    }"#;
+
+    let doc: Document<Object> = serde_json::from_str(source)?;
+    let error = validate::document(&doc)
+        .into_iter()
+        .map(ErrorObject::from)
+        .next()
+        .expect("expected a violation");
+
+    assert_eq!(error.code.as_deref(), Some("unlinked-resource"));
+    assert_eq!(error.status.map(|status| status.as_u16()), Some(400));
+    assert_eq!(
+        error.source.and_then(|source| source.pointer),
+        Some("/included/0".to_owned())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,233 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use http::{header, HeaderMap, Request, Response};
+use http_body::Body;
+use http_body_util::{Either, Full};
+use json_api::{
+    doc::ErrorObject,
+    http_ext,
+    media_type::{self, MEDIA_TYPE},
+    Error,
+};
+use pin_project_lite::pin_project;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// The body of a response returned by [`JsonApiService`]. Either the body of the inner
+/// service, or an error document.
+///
+/// [`JsonApiService`]: ./struct.JsonApiService.html
+pub type ResponseBody<B> = Either<B, Full<<B as Body>::Data>>;
+
+/// A [`Layer`] that applies [`JsonApiService`] to a service.
+///
+/// [`Layer`]: https://docs.rs/tower/0.5/tower/trait.Layer.html
+/// [`JsonApiService`]: ./struct.JsonApiService.html
+#[derive(Clone, Debug, Default)]
+pub struct JsonApiLayer {
+    extensions: Vec<String>,
+}
+
+impl JsonApiLayer {
+    /// Returns a new `JsonApiLayer` that does not support any extensions.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds the URI of an extension that the wrapped service supports. Requests that
+    /// apply other extensions are rejected.
+    pub fn extension<T: Into<String>>(mut self, uri: T) -> Self {
+        self.extensions.push(uri.into());
+        self
+    }
+}
+
+impl<S> Layer<S> for JsonApiLayer {
+    type Service = JsonApiService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        JsonApiService {
+            inner,
+            extensions: self.extensions.clone(),
+        }
+    }
+}
+
+/// A service that enforces the content negotiation rules of the JSON API specification
+/// and normalizes error responses.
+///
+/// A request is rejected with `415 Unsupported Media Type` or `406 Not Acceptable` and
+/// an error document if its `Content-Type` or `Accept` header violates the rules of
+/// [`media_type::negotiate`]. Otherwise, the request is passed to the inner service.
+/// A request with a `Content-Type` other than the JSON API media type, like a form
+/// post or a file upload, is passed to the inner service without a `415` check.
+///
+/// If the inner service responds with a `4xx` or `5xx` status and a body that is empty
+/// or plain text, the body is replaced with an error document that contains
+/// `ErrorObject::new(status)`. Error responses that already contain a JSON API
+/// document are not changed.
+///
+/// [`media_type::negotiate`]: https://docs.rs/json-api/0.4/json_api/media_type/fn.negotiate.html
+#[derive(Clone, Debug)]
+pub struct JsonApiService<S> {
+    inner: S,
+    extensions: Vec<String>,
+}
+
+impl<S> JsonApiService<S> {
+    /// Consumes the `JsonApiService` and returns the wrapped service.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn negotiate(&self, headers: &HeaderMap) -> Result<(), Error> {
+        let extensions = self
+            .extensions
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+
+        // Requests that do not contain a JSON API document, like form posts, are not
+        // checked for a supported media type.
+        let is_document = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .is_some_and(|essence| essence.trim().eq_ignore_ascii_case(MEDIA_TYPE));
+
+        if is_document {
+            http_ext::negotiate(headers, &extensions)?;
+        } else {
+            let accept = headers
+                .get(header::ACCEPT)
+                .map(|value| value.to_str().unwrap_or_default());
+
+            media_type::negotiate(None, accept, &extensions)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for JsonApiService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    ResBody: Body,
+    ResBody::Data: From<Vec<u8>>,
+{
+    type Response = Response<ResponseBody<ResBody>>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future, ResBody>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        match self.negotiate(req.headers()) {
+            Ok(()) => ResponseFuture::Inner {
+                future: self.inner.call(req),
+            },
+            Err(e) => ResponseFuture::Rejected {
+                response: Some(error_response(ErrorObject::from(&e))),
+            },
+        }
+    }
+}
+
+pin_project! {
+    /// The future returned by [`JsonApiService`].
+    ///
+    /// [`JsonApiService`]: ./struct.JsonApiService.html
+    #[project = ResponseFutureProj]
+    pub enum ResponseFuture<F, B>
+    where
+        B: Body,
+    {
+        /// Waits for the response of the inner service.
+        Inner {
+            #[pin]
+            future: F,
+        },
+
+        /// Responds with an error document without calling the inner service.
+        Rejected {
+            response: Option<Response<ResponseBody<B>>>,
+        },
+    }
+}
+
+impl<F, B, E> Future for ResponseFuture<F, B>
+where
+    F: Future<Output = Result<Response<B>, E>>,
+    B: Body,
+    B::Data: From<Vec<u8>>,
+{
+    type Output = Result<Response<ResponseBody<B>>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            ResponseFutureProj::Inner { future } => match future.poll(cx) {
+                Poll::Ready(Ok(resp)) => Poll::Ready(Ok(normalize(resp))),
+                Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+                Poll::Pending => Poll::Pending,
+            },
+            ResponseFutureProj::Rejected { response } => Poll::Ready(Ok(response
+                .take()
+                .expect("ResponseFuture polled after completion"))),
+        }
+    }
+}
+
+/// Replaces the body of an error response that does not contain a JSON API document
+/// with an error document. Other responses are returned as is.
+fn normalize<B>(resp: Response<B>) -> Response<ResponseBody<B>>
+where
+    B: Body,
+    B::Data: From<Vec<u8>>,
+{
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_ascii_lowercase());
+
+    let replace = (status.is_client_error() || status.is_server_error())
+        && match content_type {
+            None => true,
+            Some(ref value) if value.starts_with(MEDIA_TYPE) => false,
+            Some(ref value) => value.starts_with("text/plain") || resp.body().is_end_stream(),
+        };
+
+    if !replace {
+        return resp.map(Either::Left);
+    }
+
+    let (mut parts, _) = resp.into_parts();
+    let object = ErrorObject::new(Some(status));
+    let (error, body) = error_response::<B>(object).into_parts();
+
+    // The length and type of the original body no longer apply.
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.remove(header::CONTENT_TYPE);
+
+    if let Some(value) = error.headers.get(header::CONTENT_TYPE) {
+        parts.headers.insert(header::CONTENT_TYPE, value.clone());
+    }
+
+    Response::from_parts(parts, body)
+}
+
+/// Returns a response that contains an error document with `object`. The status of the
+/// response is the status of `object`. If the document can not be serialized, the
+/// response has an empty body and no `Content-Type`.
+fn error_response<B>(object: ErrorObject) -> Response<ResponseBody<B>>
+where
+    B: Body,
+    B::Data: From<Vec<u8>>,
+{
+    http_ext::error_response(object).map(|body| Either::Right(Full::new(body.into())))
+}
@@ -0,0 +1,34 @@
+//! Tower support for the json-api crate.
+//!
+//! [`JsonApiLayer`] wraps any service that handles [`http`] requests. It enforces the
+//! content negotiation rules of the JSON API specification, and replaces error
+//! responses that do not contain a JSON API document with an error document, like the
+//! catcher of `json-api-rocket` does. The layer can be used with hyper, axum, tonic-web
+//! and any other stack that is built on tower.
+//!
+//! # Example
+//!
+//! ```
+//! # extern crate json_api_tower;
+//! # extern crate tower;
+//! #
+//! use json_api_tower::JsonApiLayer;
+//! use tower::ServiceBuilder;
+//!
+//! # fn main() {
+//! # let handler = tower::service_fn(|_: http::Request<String>| async {
+//! #     Ok::<_, std::convert::Infallible>(http::Response::new(String::new()))
+//! # });
+//! let service = ServiceBuilder::new()
+//!     .layer(JsonApiLayer::new())
+//!     .service(handler);
+//! # let _ = service;
+//! # }
+//! ```
+//!
+//! [`JsonApiLayer`]: ./struct.JsonApiLayer.html
+//! [`http`]: https://docs.rs/http/1
+
+mod layer;
+
+pub use self::layer::{JsonApiLayer, JsonApiService, ResponseBody, ResponseFuture};
@@ -0,0 +1,146 @@
+use std::convert::Infallible;
+
+use bytes::Bytes;
+use http::{header, Request, Response, StatusCode};
+use http_body_util::{BodyExt, Full};
+use json_api_tower::JsonApiLayer;
+use serde_json::{json, Value};
+use tower::{service_fn, ServiceBuilder, ServiceExt};
+
+const ATOMIC: &str = "https://jsonapi.org/ext/atomic";
+
+async fn call(req: Request<String>, resp: Response<Full<Bytes>>) -> Response<Value> {
+    let service = ServiceBuilder::new()
+        .layer(JsonApiLayer::new().extension(ATOMIC))
+        .service(service_fn(move |_: Request<String>| {
+            let resp = resp.clone();
+            async move { Ok::<_, Infallible>(resp) }
+        }));
+
+    let (parts, body) = service.oneshot(req).await.unwrap().into_parts();
+    let bytes = body.collect().await.unwrap().to_bytes();
+    let value = match bytes.len() {
+        0 => Value::Null,
+        _ => serde_json::from_slice(&bytes).unwrap(),
+    };
+
+    Response::from_parts(parts, value)
+}
+
+fn request(content_type: Option<&str>, accept: Option<&str>) -> Request<String> {
+    let mut builder = Request::post("/posts");
+
+    if let Some(value) = content_type {
+        builder = builder.header(header::CONTENT_TYPE, value);
+    }
+
+    if let Some(value) = accept {
+        builder = builder.header(header::ACCEPT, value);
+    }
+
+    builder.body(String::new()).unwrap()
+}
+
+fn response(
+    status: StatusCode,
+    content_type: Option<&str>,
+    body: &'static str,
+) -> Response<Full<Bytes>> {
+    let mut builder = Response::builder().status(status);
+
+    if let Some(value) = content_type {
+        builder = builder.header(header::CONTENT_TYPE, value);
+    }
+
+    builder
+        .body(Full::new(Bytes::from_static(body.as_bytes())))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn rejects_unsupported_content_type() {
+    let req = request(Some("application/vnd.api+json; charset=utf-8"), None);
+    let resp = call(req, response(StatusCode::OK, None, "")).await;
+
+    assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(
+        resp.headers()[header::CONTENT_TYPE],
+        "application/vnd.api+json"
+    );
+    assert_eq!(resp.body()["errors"][0]["status"], "415");
+}
+
+#[tokio::test]
+async fn passes_other_content_types() {
+    for value in &[
+        "multipart/form-data; boundary=x",
+        "application/x-www-form-urlencoded",
+        "text/plain; charset=utf-8",
+    ] {
+        let req = request(Some(value), None);
+        let resp = call(req, response(StatusCode::NO_CONTENT, None, "")).await;
+
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT, "{}", value);
+    }
+}
+
+#[tokio::test]
+async fn rejects_unacceptable_accept() {
+    let req = request(None, Some("application/vnd.api+json; charset=utf-8"));
+    let resp = call(req, response(StatusCode::OK, None, "")).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_ACCEPTABLE);
+    assert_eq!(resp.body()["errors"][0]["status"], "406");
+}
+
+#[tokio::test]
+async fn accepts_supported_extensions() {
+    let value = format!(r#"application/vnd.api+json; ext="{}""#, ATOMIC);
+    let req = request(Some(&value), Some(&value));
+    let resp = call(req, response(StatusCode::NO_CONTENT, None, "")).await;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    assert_eq!(*resp.body(), Value::Null);
+}
+
+#[tokio::test]
+async fn replaces_plain_error_responses() {
+    let inner = response(StatusCode::NOT_FOUND, Some("text/plain"), "Not Found");
+    let resp = call(request(None, None), inner).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+        resp.headers()[header::CONTENT_TYPE],
+        "application/vnd.api+json"
+    );
+    assert_eq!(
+        resp.body()["errors"],
+        json!([{ "status": "404", "title": "Not Found" }])
+    );
+
+    let inner = response(StatusCode::INTERNAL_SERVER_ERROR, None, "");
+    let resp = call(request(None, None), inner).await;
+
+    assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(resp.body()["errors"][0]["status"], "500");
+}
+
+#[tokio::test]
+async fn preserves_json_api_responses() {
+    let body = r#"{"errors":[{"status":"422","title":"Invalid title"}]}"#;
+    let inner = response(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Some("application/vnd.api+json"),
+        body,
+    );
+    let resp = call(request(None, None), inner).await;
+
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(resp.body()["errors"][0]["title"], "Invalid title");
+
+    let inner = response(StatusCode::OK, Some("application/json"), r#"{"ok":true}"#);
+    let resp = call(request(None, None), inner).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(*resp.body(), json!({ "ok": true }));
+}